@@ -1,90 +1,73 @@
-use std::error::Error;
-use std::fs::File;
-use std::io::BufReader;
+use std::env;
 use std::process;
-use std::str::FromStr;
 
-use serde::Deserialize;
+use intcode::{evaluate_symbolic, find_noun_verb, parse_input};
 
-#[derive(Deserialize, Debug)]
-struct Record {
-    opcodes: String,
-}
-
-fn parse_input(file_path: String) -> Result<Vec<u32>, Box<dyn Error>> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-    let mut rdr = csv::ReaderBuilder::new().delimiter(b'\'').has_headers(false).from_reader(reader);
+const TARGET: i64 = 19690720;
 
-    let mut vec: Vec<u32> = vec![];
+fn solve_symbolic(input: &[i64], target: i64) {
+    let expr = match evaluate_symbolic(input) {
+        Ok(expr) => expr,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
 
-    for result in rdr.deserialize() {
-        let record: Record = result?;
+    let polynomial = match expr.to_polynomial() {
+        Some(polynomial) => polynomial,
+        None => {
+            println!("Error: closed form overflows");
+            process::exit(1);
+        }
+    };
 
-        let stuff: Vec<&str> = record.opcodes.split(',').collect();
+    println!("Closed form :: opcodes[0] = {}", polynomial);
 
-        for thing in stuff {
-            vec.push(u32::from_str(thing).unwrap());
+    match polynomial.solve(target).first() {
+        Some((noun, verb)) => println!("Final result :: {} :: {}", noun, verb),
+        None => {
+            println!("No noun and verb produce {}", target);
+            process::exit(1);
         }
     }
-
-    Ok(vec)
 }
 
-fn process_program(opcodes: &mut Vec<u32>, verb: u32, noun: u32) -> Result<u32, Box<dyn Error>> {
-    let mut next_position: usize = 0;
-
-    opcodes[1] = verb;
-    opcodes[2] = noun;
-
-    loop {
-        let opcode = opcodes[next_position];
-        let first = opcodes[next_position + 1];
-        let second = opcodes[next_position + 2];
-        let dest = opcodes[next_position + 3];
-
-        let operand_left = opcodes[first as usize];
-        let operand_right = opcodes[second as usize];
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let symbolic = args.iter().any(|arg| arg == "--symbolic");
 
-        let result = match opcode {
-            1 => operand_left + operand_right,
-            2 => operand_left * operand_right,
-            99 => break,
-            _ => panic!("Unknown opcode: {}", opcode),
-        };
+    args.retain(|arg| arg != "--symbolic");
 
-        opcodes[dest as usize] = result;
+    let target = match args.first() {
+        Some(target) => match target.parse() {
+            Ok(target) => target,
+            Err(e) => {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        None => TARGET,
+    };
+
+    let input = match parse_input("./src/opcodes.csv") {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
 
-        next_position += 4;
+    if symbolic {
+        solve_symbolic(&input, target);
+        return;
     }
 
-    Ok(opcodes[0])
-}
-
-fn main() {
-    for noun in 0..99 {
-        for verb in 0..99 {
-            let mut input = match parse_input(String::from("./src/opcodes.csv")) {
-                Ok(vector) => vector,
-                Err(e) => {
-                    println!("Error: {}", e);
-                    process::exit(1);
-                }
-            };
-
-            match process_program(&mut input, noun, verb) {
-                Ok(number) => match number {
-                    19690720 => {
-                        println!("Final result :: {} :: {}", noun, verb);
-                        break;
-                    },
-                    _ => continue,
-                },
-                Err(e) => {
-                    println!("Error: {}", e);
-                    process::exit(1);
-                },
-            }
+    match find_noun_verb(&input, target) {
+        Some((noun, verb)) => println!("Final result :: {} :: {}", noun, verb),
+        None => {
+            println!("No noun and verb produce {}", target);
+            process::exit(1);
         }
     }
 }
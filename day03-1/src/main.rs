@@ -1,10 +1,9 @@
 use std::error::Error;
 use std::fs::File;
 use std::io::BufReader;
-use std::collections::HashMap;
 use std::str::FromStr;
-use std::i32;
 
+use grid::{Grid, Point};
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
@@ -20,34 +19,7 @@ type WireMemo = Vec<bool>;
 
 struct WireMomento {
     box_size: usize,
-    momento: HashMap<String, WireMemo>,
-}
-
-struct PortCoordinate {
-    x: i32,
-    y: i32,
-}
-
-fn get_coordinate_hash(coord: &PortCoordinate) -> Result<String, Box<dyn Error>> {
-    let format_int = |x: i32| {
-        if x < 0 {
-            format!("-{:0>10}", i32::abs(x).to_string())
-        } else {
-            format!("{:0>11}", x.to_string())
-        }
-    };
-
-    let hash = format!("{}{}", format_int(coord.x), format_int(coord.y));
-
-    Ok(hash)
-}
-
-fn get_coordinate_from_hash(hash: String) -> Result<PortCoordinate, Box<dyn Error>> {
-    let x = i32::from_str(&hash[..11]).unwrap();
-    let y = i32::from_str(&hash[11..]).unwrap();
-    let coord = PortCoordinate { x, y };
-
-    Ok(coord)
+    momento: Grid<WireMemo>,
 }
 
 fn parse_input(file_path: &str) -> Result<WireBox, Box<dyn Error>> {
@@ -74,74 +46,64 @@ fn parse_input(file_path: &str) -> Result<WireBox, Box<dyn Error>> {
     Ok(wire_box)
 }
 
-fn get_target(direction: String) -> Result<PortCoordinate, Box<dyn Error>> {
+fn get_target(direction: String) -> Result<Point, Box<dyn Error>> {
     let heading = &direction[0..1];
-    let magnitude = i32::from_str(&direction[1..]).unwrap();
+    let magnitude = i64::from_str(&direction[1..]).unwrap();
 
     Ok(match heading {
-        "D" => PortCoordinate { x: 0, y: -magnitude},
-        "U" => PortCoordinate { x: 0, y: magnitude},
-        "L" => PortCoordinate { x: -magnitude, y: 0},
-        "R" => PortCoordinate { x: magnitude, y: 0},
+        "D" => Point { x: 0, y: -magnitude},
+        "U" => Point { x: 0, y: magnitude},
+        "L" => Point { x: -magnitude, y: 0},
+        "R" => Point { x: magnitude, y: 0},
         _ => panic!("At the disco!"),
     })
 }
 
 fn traverse_wire(wire: &Wire, wire_id: u32, momento: &mut WireMomento) {
-    let mut wire_pointer = PortCoordinate { x: 0, y: 0 };
+    let mut wire_pointer = Point { x: 0, y: 0 };
 
     for path in wire.iter() {
         let target = get_target(path.to_string()).unwrap();
         let is_horizontal = target.x != 0;
 
-        let mut wire_ptr: i32 = if is_horizontal { wire_pointer.x } else { wire_pointer.y };
-        let ptr: i32 = if is_horizontal { target.x } else { target.y };
+        let mut wire_ptr: i64 = if is_horizontal { wire_pointer.x } else { wire_pointer.y };
+        let ptr: i64 = if is_horizontal { target.x } else { target.y };
 
         let target_num = wire_ptr + ptr;
 
         let iter = if ptr > 0 { wire_ptr..(target_num + 1) } else { target_num..(wire_ptr + 1) };
 
         for step in iter {
-            let current_step =  PortCoordinate {
+            let current_step =  Point {
                 x: if is_horizontal { wire_ptr } else { wire_pointer.x},
                 y: if !is_horizontal { wire_ptr } else { wire_pointer.y},
             };
 
-            let key = get_coordinate_hash(&current_step).unwrap();
-
-
-            if !momento.momento.contains_key(&key) {
-                let fresh_memo: WireMemo = vec![false; momento.box_size];
+            let box_size = momento.box_size;
+            let wire_memo = momento.momento.entry(current_step).or_insert_with(|| vec![false; box_size]);
 
-                momento.momento.insert(key.to_string(), fresh_memo);
-            }
-
-            let mut wire_memo = momento.momento.get(&key).unwrap().to_vec();
             wire_memo[wire_id as usize] = true;
 
-            momento.momento.insert(key.to_string(), wire_memo);
-
             wire_ptr = step;
         }
 
-        wire_pointer = PortCoordinate {
+        wire_pointer = Point {
             x: if is_horizontal { target_num } else { wire_pointer.x},
             y: if !is_horizontal { target_num } else { wire_pointer.y},
         };
     }
 }
 
-fn find_shortest_intersection_distance(intersections: Vec<String>, cost_fn: &dyn Fn(&PortCoordinate, &PortCoordinate) -> i32) -> i32 {
-    let origin = PortCoordinate {
+fn find_shortest_intersection_distance(intersections: Vec<Point>, cost_fn: &dyn Fn(&Point, &Point) -> i64) -> i64 {
+    let origin = Point {
         x: 0,
         y: 0,
     };
 
-    let mut min_cost: i32 = i32::MAX;
+    let mut min_cost: i64 = i64::MAX;
 
     for intersection in intersections {
-        let coord = get_coordinate_from_hash(intersection).unwrap();
-        let cost = cost_fn(&coord, &origin);
+        let cost = cost_fn(&intersection, &origin);
 
         if cost <= min_cost && cost > 0 {
             min_cost = cost;
@@ -153,34 +115,26 @@ fn find_shortest_intersection_distance(intersections: Vec<String>, cost_fn: &dyn
 
 fn main() {
     let wire_box: WireBox = parse_input("./src/directions.csv").unwrap();
-    let momento_map: HashMap<String, WireMemo> = HashMap::new();
-
     let mut momento = WireMomento {
         box_size: wire_box.len(),
-        momento: momento_map,
+        momento: Grid::new(),
     };
 
     for (wire_id, wire) in wire_box.iter().enumerate() {
         traverse_wire(wire, wire_id as u32, &mut momento);
     }
 
-    let mut intersections: Vec<String> = vec![];
-
-    for key in momento.momento.keys() {
-        let memo = momento.momento.get(key).unwrap();
+    let mut intersections: Vec<Point> = vec![];
 
-        let intersection = memo.iter().fold(true, |a, b| a & b);
+    for (point, memo) in &momento.momento {
+        let intersection = memo.iter().all(|wire| *wire);
 
         if intersection {
-            intersections.push(String::from(key));
+            intersections.push(*point);
         }
     }
 
-    let manhattan_dist = |x: &PortCoordinate, y: &PortCoordinate| {
-        let result: i32 = i32::abs(x.x - y.x) + i32::abs(y.y - x.y);
-
-        result
-    };
+    let manhattan_dist = |x: &Point, y: &Point| x.manhattan_distance(y);
 
     let result = find_shortest_intersection_distance(intersections, &manhattan_dist);
 
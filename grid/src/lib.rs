@@ -0,0 +1,84 @@
+use std::collections::hash_map::{Entry, Iter};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan_distance(&self, other: &Point) -> i64 {
+        i64::abs(self.x - other.x) + i64::abs(self.y - other.y)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Grid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Grid<T> {
+        Grid { cells: HashMap::new() }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new() -> Grid<T> {
+        Grid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.cells.get(point)
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.cells.get_mut(point)
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn entry(&mut self, point: Point) -> Entry<'_, Point, T> {
+        self.cells.entry(point)
+    }
+
+    pub fn iter(&self) -> Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(min, max), point| {
+            (
+                Point::new(i64::min(min.x, point.x), i64::min(min.y, point.y)),
+                Point::new(i64::max(max.x, point.x), i64::max(max.y, point.y)),
+            )
+        }))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = (&'a Point, &'a T);
+    type IntoIter = Iter<'a, Point, T>;
+
+    fn into_iter(self) -> Iter<'a, Point, T> {
+        self.cells.iter()
+    }
+}
@@ -0,0 +1,107 @@
+use std::error::Error;
+
+use crate::io::Queue;
+use crate::machine::Machine;
+
+pub type PhaseSetting = (Vec<i64>, i64);
+
+pub fn run_chain(program: &[i64], phases: &[i64]) -> Result<i64, Box<dyn Error>> {
+    let mut signal = 0;
+
+    for phase in phases {
+        let mut machine = Machine::new(program.to_vec());
+        let output = machine.run_with_input(vec![*phase, signal])?;
+
+        signal = match output.last() {
+            Some(value) => *value,
+            None => return Err(format!("Amplifier with phase {} produced no output", phase).into()),
+        };
+    }
+
+    Ok(signal)
+}
+
+pub fn run_feedback(program: &[i64], phases: &[i64]) -> Result<i64, Box<dyn Error>> {
+    let mut machines: Vec<Machine> = phases.iter().map(|_| Machine::new(program.to_vec())).collect();
+    let mut queues: Vec<Queue> = phases.iter().map(|phase| Queue::new(vec![*phase])).collect();
+    let mut signal = None;
+
+    if let Some(queue) = queues.first_mut() {
+        queue.push(0);
+    }
+
+    while !machines.iter().all(|machine| machine.is_halted()) {
+        let mut progressed = false;
+
+        for index in 0..machines.len() {
+            if machines[index].is_halted() {
+                continue;
+            }
+
+            let ip = machines[index].ip();
+
+            machines[index].run(&mut queues[index])?;
+
+            let output = queues[index].take_output();
+            let next = (index + 1) % machines.len();
+
+            progressed |= !output.is_empty() || machines[index].ip() != ip || machines[index].is_halted();
+
+            for value in output {
+                if index == machines.len() - 1 {
+                    signal = Some(value);
+                }
+
+                queues[next].push(value);
+            }
+        }
+
+        if !progressed {
+            return Err("Amplifier feedback loop deadlocked waiting for input".into());
+        }
+    }
+
+    match signal {
+        Some(signal) => Ok(signal),
+        None => Err("Amplifier feedback loop produced no output".into()),
+    }
+}
+
+pub fn permutations(values: &[i64]) -> Vec<Vec<i64>> {
+    if values.len() <= 1 {
+        return vec![values.to_vec()];
+    }
+
+    let mut result = vec![];
+
+    for (index, value) in values.iter().enumerate() {
+        let mut rest = values.to_vec();
+
+        rest.remove(index);
+
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, *value);
+            result.push(permutation);
+        }
+    }
+
+    result
+}
+
+pub fn best_phases(program: &[i64], phases: &[i64], feedback: bool) -> Result<Option<PhaseSetting>, Box<dyn Error>> {
+    let mut best: Option<PhaseSetting> = None;
+
+    for permutation in permutations(phases) {
+        let signal = if feedback {
+            run_feedback(program, &permutation)?
+        } else {
+            run_chain(program, &permutation)?
+        };
+
+        if best.as_ref().is_none_or(|(_, best_signal)| signal > *best_signal) {
+            best = Some((permutation, signal));
+        }
+    }
+
+    Ok(best)
+}
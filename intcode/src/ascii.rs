@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+
+use crate::io::Io;
+
+const MAX_ASCII: i64 = 127;
+const NEWLINE: i64 = 10;
+
+pub struct Ascii<W: Write> {
+    script: Option<VecDeque<String>>,
+    input: VecDeque<i64>,
+    line: String,
+    values: Vec<i64>,
+    out: W,
+    error: Option<io::Error>,
+}
+
+pub fn encode(command: &str) -> Vec<i64> {
+    command.chars().map(|c| c as i64).chain(Some(NEWLINE)).collect()
+}
+
+impl<W: Write> Ascii<W> {
+    pub fn interactive(out: W) -> Ascii<W> {
+        Ascii {
+            script: None,
+            input: VecDeque::new(),
+            line: String::new(),
+            values: vec![],
+            out,
+            error: None,
+        }
+    }
+
+    pub fn scripted(commands: Vec<String>, out: W) -> Ascii<W> {
+        Ascii {
+            script: Some(commands.into_iter().collect()),
+            ..Ascii::interactive(out)
+        }
+    }
+
+    pub fn send(&mut self, command: &str) {
+        self.input.extend(encode(command));
+    }
+
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    fn next_command(&mut self) -> Option<String> {
+        if let Some(script) = &mut self.script {
+            return script.pop_front();
+        }
+
+        if let Err(e) = self.out.flush() {
+            self.error.get_or_insert(e);
+        }
+
+        let mut command = String::new();
+        let stdin = io::stdin();
+
+        match stdin.lock().read_line(&mut command) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(String::from(command.trim_end_matches(['\r', '\n']))),
+        }
+    }
+
+    fn emit(&mut self, text: &str) {
+        if self.error.is_none() {
+            if let Err(e) = writeln!(self.out, "{}", text) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    fn flush_line(&mut self) {
+        if !self.line.is_empty() {
+            let line = std::mem::take(&mut self.line);
+
+            self.emit(&line);
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_line();
+
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        self.out.flush()?;
+
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> Io for Ascii<W> {
+    fn read(&mut self) -> Option<i64> {
+        if self.input.is_empty() {
+            self.flush_line();
+
+            let command = self.next_command()?;
+
+            self.send(&command);
+        }
+
+        self.input.pop_front()
+    }
+
+    fn write(&mut self, value: i64) {
+        match value {
+            NEWLINE => {
+                let line = std::mem::take(&mut self.line);
+
+                self.emit(&line);
+            },
+            0..=MAX_ASCII => self.line.push(value as u8 as char),
+            _ => {
+                self.flush_line();
+                self.emit(&value.to_string());
+                self.values.push(value);
+            },
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::cell::Cell;
+use crate::disasm::Mode;
+use crate::isa::{get_standard_set, InstructionSet};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsmError {
+    pub line: usize,
+    pub message: String,
+}
+
+enum Item<'a> {
+    Instruction(i64, Vec<&'a str>),
+    Data(Vec<&'a str>),
+}
+
+struct Statement<'a> {
+    line: usize,
+    item: Item<'a>,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AsmError {}
+
+fn error<T>(line: usize, message: String) -> Result<T, AsmError> {
+    Err(AsmError { line, message })
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        },
+        _ => false,
+    }
+}
+
+fn split_operands(text: &str) -> Vec<&str> {
+    if text.trim().is_empty() {
+        return vec![];
+    }
+
+    text.split(',').map(|operand| operand.trim()).collect()
+}
+
+fn parse_statements<'a, C: Cell>(instructions: &InstructionSet<C>, source: &'a str) -> Result<(Vec<Statement<'a>>, HashMap<String, i64>), AsmError> {
+    let mut statements = vec![];
+    let mut labels: HashMap<String, i64> = HashMap::new();
+    let mut address: i64 = 0;
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut text = raw.split(';').next().unwrap_or("").trim();
+
+        while let Some(colon) = text.find(':') {
+            let label = text[..colon].trim();
+
+            if !is_label(label) {
+                return error(line, format!("invalid label '{}'", label));
+            }
+
+            if labels.insert(String::from(label), address).is_some() {
+                return error(line, format!("duplicate label '{}'", label));
+            }
+
+            text = text[colon + 1..].trim();
+        }
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let (mnemonic, rest) = match text.find(char::is_whitespace) {
+            Some(space) => (&text[..space], &text[space..]),
+            None => (text, ""),
+        };
+        let operands = split_operands(rest);
+
+        let item = if mnemonic.eq_ignore_ascii_case("data") {
+            if operands.is_empty() {
+                return error(line, String::from("data directive needs at least one value"));
+            }
+
+            address += operands.len() as i64;
+            Item::Data(operands)
+        } else {
+            let (opcode, name, arity) = match instructions.find(mnemonic) {
+                Some((opcode, instruction)) => (opcode, instruction.mnemonic, instruction.arity),
+                None => return error(line, format!("unknown mnemonic '{}'", mnemonic)),
+            };
+
+            if operands.len() != arity {
+                return error(line, format!("{} takes {} operands, found {}", name, arity, operands.len()));
+            }
+
+            address += arity as i64 + 1;
+            Item::Instruction(opcode, operands)
+        };
+
+        statements.push(Statement { line, item });
+    }
+
+    Ok((statements, labels))
+}
+
+fn parse_value(text: &str, labels: &HashMap<String, i64>) -> Result<i64, String> {
+    let text = text.trim();
+
+    if let Ok(value) = text.parse() {
+        return Ok(value);
+    }
+
+    let (name, offset) = match text.find(['+', '-']) {
+        Some(sign) => {
+            let offset: i64 = match text[sign + 1..].trim().parse() {
+                Ok(offset) => offset,
+                Err(_) => return Err(format!("invalid offset in '{}'", text)),
+            };
+
+            let offset = match &text[sign..sign + 1] {
+                "-" => offset.checked_neg(),
+                _ => Some(offset),
+            };
+
+            match offset {
+                Some(offset) => (text[..sign].trim(), offset),
+                None => return Err(format!("offset out of range in '{}'", text)),
+            }
+        },
+        None => (text, 0),
+    };
+
+    match labels.get(name).map(|address| i64::checked_add(*address, offset)) {
+        Some(Some(address)) => Ok(address),
+        Some(None) => Err(format!("address out of range in '{}'", text)),
+        None => Err(format!("unknown label '{}'", name)),
+    }
+}
+
+fn parse_operand(text: &str, labels: &HashMap<String, i64>) -> Result<(Mode, i64), String> {
+    if text.starts_with('[') && text.ends_with(']') {
+        return Ok((Mode::Position, parse_value(&text[1..text.len() - 1], labels)?));
+    }
+
+    if let Some(value) = text.strip_prefix('#') {
+        return Ok((Mode::Immediate, parse_value(value, labels)?));
+    }
+
+    if let Some(offset) = text.strip_prefix("rb") {
+        let offset = offset.trim();
+
+        return match offset.chars().next() {
+            None => Ok((Mode::Relative, 0)),
+            Some('+') => Ok((Mode::Relative, parse_value(&offset[1..], labels)?)),
+            Some('-') => match parse_value(&offset[1..], labels)?.checked_neg() {
+                Some(offset) => Ok((Mode::Relative, offset)),
+                None => Err(format!("offset out of range in '{}'", text)),
+            },
+            _ => Err(format!("invalid relative operand '{}'", text)),
+        };
+    }
+
+    Err(format!("operand '{}' needs an addressing mode: [x], #x or rb+x", text))
+}
+
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    assemble_with(get_standard_set(), source)
+}
+
+pub fn assemble_with<C: Cell>(instructions: &InstructionSet<C>, source: &str) -> Result<Vec<i64>, AsmError> {
+    let (statements, labels) = parse_statements(instructions, source)?;
+    let mut program = vec![];
+
+    for statement in statements {
+        let line = statement.line;
+
+        match statement.item {
+            Item::Data(values) => {
+                for value in values {
+                    match parse_value(value, &labels) {
+                        Ok(value) => program.push(value),
+                        Err(message) => return error(line, message),
+                    }
+                }
+            },
+            Item::Instruction(opcode, operands) => {
+                let mut instruction = opcode;
+                let mut values = vec![];
+
+                for (n, operand) in operands.iter().enumerate() {
+                    let (mode, value) = match parse_operand(operand, &labels) {
+                        Ok(operand) => operand,
+                        Err(message) => return error(line, message),
+                    };
+                    let digit = match mode {
+                        Mode::Position => 0,
+                        Mode::Immediate => 1,
+                        Mode::Relative => 2,
+                    };
+
+                    instruction += digit * 10_i64.pow(n as u32 + 2);
+                    values.push(value);
+                }
+
+                program.push(instruction);
+                program.extend(values);
+            },
+        }
+    }
+
+    Ok(program)
+}
+
+pub fn to_csv(program: &[i64]) -> String {
+    let words: Vec<String> = program.iter().map(|word| word.to_string()).collect();
+
+    words.join(",")
+}
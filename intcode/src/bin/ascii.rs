@@ -0,0 +1,50 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::process;
+
+use intcode::{parse_input, Ascii, Machine};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 {
+        println!("Usage: ascii <program> [script]");
+        process::exit(1);
+    }
+
+    let program = match parse_input(&args[1]) {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut adapter = match args.get(2) {
+        Some(script_path) => match fs::read_to_string(script_path) {
+            Ok(script) => Ascii::scripted(script.lines().map(String::from).collect(), stdout.lock()),
+            Err(e) => {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        None => Ascii::interactive(stdout.lock()),
+    };
+
+    let result = Machine::new(program).run(&mut adapter);
+
+    if let Err(e) = adapter.finish() {
+        println!("Error: {}", e);
+        process::exit(1);
+    }
+
+    match result {
+        Ok(status) => println!("Finished :: {:?}", status),
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use intcode::{assemble, to_csv};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 {
+        println!("Usage: asm <source> [output]");
+        process::exit(1);
+    }
+
+    let source = match fs::read_to_string(&args[1]) {
+        Ok(source) => source,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let program = match assemble(&source) {
+        Ok(program) => program,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    match args.get(2) {
+        Some(output) => {
+            if let Err(e) = fs::write(output, to_csv(&program) + "\n") {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        None => println!("{}", to_csv(&program)),
+    }
+}
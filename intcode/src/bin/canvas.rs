@@ -0,0 +1,75 @@
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process;
+
+use intcode::{parse_input, Canvas, Io, Machine, Robot, VmError};
+
+fn run(machine: &mut Machine, io: &mut dyn Io) -> Result<(), VmError> {
+    machine.run(io)?;
+
+    Ok(())
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let robot = args.iter().any(|arg| arg == "--robot");
+
+    args.retain(|arg| arg != "--robot");
+
+    if args.is_empty() {
+        println!("Usage: canvas <program> [image.ppm] [--robot]");
+        process::exit(1);
+    }
+
+    let program = match parse_input(&args[0]) {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut machine = Machine::new(program);
+
+    let (canvas, result) = if robot {
+        let mut robot = Robot::new(Canvas::new());
+
+        robot.canvas.paint(robot.position, 1);
+
+        let result = run(&mut machine, &mut robot);
+
+        (robot.canvas, result)
+    } else {
+        let mut canvas = Canvas::new();
+
+        canvas.set_glyph(2, '+');
+        canvas.set_glyph(3, '-');
+        canvas.set_glyph(4, 'o');
+
+        let result = run(&mut machine, &mut canvas);
+
+        (canvas, result)
+    };
+
+    if let Err(e) = result {
+        println!("Error: {}", e);
+        process::exit(1);
+    }
+
+    print!("{}", canvas.render());
+
+    if let Some(image_path) = args.get(1) {
+        let written = File::create(image_path).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+
+            canvas.write_ppm(&mut writer)?;
+            writer.flush()
+        });
+
+        if let Err(e) = written {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
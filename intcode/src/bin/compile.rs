@@ -0,0 +1,49 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use intcode::{compile, compile_to_asm, to_csv};
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let asm = args.iter().any(|arg| arg == "--asm");
+
+    args.retain(|arg| arg != "--asm");
+
+    if args.is_empty() {
+        println!("Usage: compile <source> [output.csv] [--asm]");
+        process::exit(1);
+    }
+
+    let source = match fs::read_to_string(&args[0]) {
+        Ok(source) => source,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let output = if asm {
+        compile_to_asm(&source)
+    } else {
+        compile(&source).map(|program| to_csv(&program) + "\n")
+    };
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    match args.get(1) {
+        Some(output_path) => {
+            if let Err(e) = fs::write(output_path, output) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        None => print!("{}", output),
+    }
+}
@@ -0,0 +1,76 @@
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process;
+
+use intcode::{parse_input, parse_values, Coverage, Machine, Queue};
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let html = match args.iter().position(|arg| arg == "--html") {
+        Some(index) if index + 1 < args.len() => {
+            let html_path = args.remove(index + 1);
+
+            args.remove(index);
+            Some(html_path)
+        },
+        Some(_) => {
+            println!("Error: --html needs a file path");
+            process::exit(1);
+        },
+        None => None,
+    };
+
+    if args.is_empty() {
+        println!("Usage: coverage <program> [inputs]... [--html report.html]");
+        process::exit(1);
+    }
+
+    let program = match parse_input(&args[0]) {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let runs = match args.len() {
+        1 => vec![vec![]],
+        _ => match args[1..].iter().map(|run| parse_values(run)).collect::<Result<Vec<_>, _>>() {
+            Ok(runs) => runs,
+            Err(e) => {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+    };
+
+    let mut coverage = Coverage::new();
+
+    for inputs in runs {
+        let mut machine = Machine::new(program.clone());
+        let mut queue = Queue::new(inputs.clone());
+
+        match machine.run_observed(&mut queue, &mut coverage) {
+            Ok(status) => println!("Inputs {:?} :: {:?} :: outputs {:?}", inputs, status, queue.output()),
+            Err(e) => println!("Inputs {:?} :: Error: {}", inputs, e),
+        }
+    }
+
+    match html {
+        Some(html_path) => {
+            let written = File::create(&html_path).and_then(|file| {
+                let mut writer = BufWriter::new(file);
+
+                coverage.write_html(&program, &mut writer)?;
+                writer.flush()
+            });
+
+            if let Err(e) = written {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        None => print!("{}", coverage.report(&program)),
+    }
+}
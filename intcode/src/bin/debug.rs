@@ -0,0 +1,26 @@
+use std::env;
+use std::io;
+use std::process;
+
+use intcode::{parse_input, Debugger, Machine};
+
+fn main() {
+    let file_path = env::args().nth(1).unwrap_or_else(|| String::from("./src/opcodes.csv"));
+
+    let input = match parse_input(&file_path) {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut debugger = Debugger::new(Machine::new(input));
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    if let Err(e) = debugger.repl(&mut stdin.lock(), &mut stdout.lock()) {
+        println!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,20 @@
+use std::env;
+use std::process;
+
+use intcode::{disassemble, parse_input};
+
+fn main() {
+    let file_path = env::args().nth(1).unwrap_or_else(|| String::from("./src/opcodes.csv"));
+
+    let input = match parse_input(&file_path) {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    for line in disassemble(&input) {
+        println!("{}", line.to_string().trim_end());
+    }
+}
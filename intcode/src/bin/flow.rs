@@ -0,0 +1,29 @@
+use std::env;
+use std::process;
+
+use intcode::{analyze, parse_input};
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let dot = args.iter().any(|arg| arg == "--dot");
+
+    args.retain(|arg| arg != "--dot");
+
+    let file_path = args.first().cloned().unwrap_or_else(|| String::from("./src/opcodes.csv"));
+
+    let input = match parse_input(&file_path) {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let flow = analyze(&input);
+
+    if dot {
+        print!("{}", flow.to_dot());
+    } else {
+        print!("{}", flow);
+    }
+}
@@ -0,0 +1,42 @@
+use std::env;
+use std::process;
+
+use intcode::fuzz::{fuzz, Backend, Driven, Interpreter, Precompiled};
+
+const FUEL: usize = 10_000;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let iterations: usize = match args.get(1).map(|arg| arg.parse()) {
+        Some(Ok(iterations)) => iterations,
+        Some(Err(e)) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        },
+        None => 10_000,
+    };
+
+    let seed: u64 = match args.get(2).map(|arg| arg.parse()) {
+        Some(Ok(seed)) => seed,
+        Some(Err(e)) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        },
+        None => 1,
+    };
+
+    let reference = Interpreter;
+    let candidates: [&dyn Backend; 2] = [&Precompiled, &Driven];
+
+    for candidate in candidates.iter() {
+        match fuzz(seed, iterations, &reference, *candidate, FUEL) {
+            Some(failure) => {
+                println!("Mismatch between {} and {} (seed {})", reference.name(), candidate.name(), seed);
+                print!("{}", failure);
+                process::exit(1);
+            },
+            None => println!("{} cases agree :: {} vs {} (seed {})", iterations, reference.name(), candidate.name(), seed),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::env;
+use std::fs::File;
+use std::io::BufWriter;
+use std::process;
+
+use intcode::{parse_input, parse_values, Machine, Profile, Queue, Tracer};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 {
+        println!("Usage: trace <program> [inputs] [trace.jsonl]");
+        process::exit(1);
+    }
+
+    let program = match parse_input(&args[1]) {
+        Ok(vector) => vector,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let inputs = match args.get(2) {
+        Some(inputs) => match parse_values(inputs) {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        None => vec![],
+    };
+
+    let mut machine = Machine::new(program);
+    let mut queue = Queue::new(inputs);
+    let mut profile = Profile::new();
+
+    let result = match args.get(3) {
+        Some(trace_path) => {
+            let file = match File::create(trace_path) {
+                Ok(file) => file,
+                Err(e) => {
+                    println!("Error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let mut observers = (Tracer::new(BufWriter::new(file)), profile);
+            let result = machine.run_observed(&mut queue, &mut observers);
+
+            profile = observers.1;
+
+            if let Err(e) = observers.0.finish() {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+
+            result
+        },
+        None => machine.run_observed(&mut queue, &mut profile),
+    };
+
+    match result {
+        Ok(status) => println!("Finished :: {:?}", status),
+        Err(e) => println!("Error: {}", e),
+    }
+
+    println!("Outputs :: {:?}", queue.output());
+    print!("{}", profile);
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use grid::{Grid, Point};
+
+use crate::io::Io;
+
+const DEFAULT_GLYPH: char = ' ';
+const DEFAULT_COLOR: [u8; 3] = [0, 0, 0];
+
+#[derive(Clone, Debug, Default)]
+pub struct Canvas {
+    grid: Grid<i64>,
+    glyphs: HashMap<i64, char>,
+    colors: HashMap<i64, [u8; 3]>,
+    pending: Vec<i64>,
+}
+
+impl Canvas {
+    pub fn new() -> Canvas {
+        let mut canvas = Canvas::default();
+
+        canvas.set_glyph(0, ' ');
+        canvas.set_glyph(1, '#');
+        canvas.set_color(1, [255, 255, 255]);
+
+        canvas
+    }
+
+    pub fn set_glyph(&mut self, tile: i64, glyph: char) {
+        self.glyphs.insert(tile, glyph);
+    }
+
+    pub fn set_color(&mut self, tile: i64, color: [u8; 3]) {
+        self.colors.insert(tile, color);
+    }
+
+    pub fn grid(&self) -> &Grid<i64> {
+        &self.grid
+    }
+
+    pub fn get(&self, point: &Point) -> Option<i64> {
+        self.grid.get(point).cloned()
+    }
+
+    pub fn paint(&mut self, point: Point, tile: i64) {
+        self.grid.insert(point, tile);
+    }
+
+    pub fn feed(&mut self, value: i64) {
+        self.pending.push(value);
+
+        if self.pending.len() == 3 {
+            let point = Point::new(self.pending[0], self.pending[1]);
+
+            self.paint(point, self.pending[2]);
+            self.pending.clear();
+        }
+    }
+
+    fn get_glyph(&self, point: &Point) -> char {
+        match self.grid.get(point) {
+            Some(tile) => self.glyphs.get(tile).cloned().unwrap_or('?'),
+            None => DEFAULT_GLYPH,
+        }
+    }
+
+    fn get_color(&self, point: &Point) -> [u8; 3] {
+        match self.grid.get(point) {
+            Some(tile) => self.colors.get(tile).cloned().unwrap_or(DEFAULT_COLOR),
+            None => DEFAULT_COLOR,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let (min, max) = match self.grid.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        let mut rendered = String::new();
+
+        for y in min.y..=max.y {
+            let row: String = (min.x..=max.x).map(|x| self.get_glyph(&Point::new(x, y))).collect();
+
+            rendered.push_str(row.trim_end());
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    pub fn write_ppm(&self, out: &mut dyn Write) -> io::Result<()> {
+        let (min, max) = self.grid.bounds().unwrap_or_default();
+
+        writeln!(out, "P6")?;
+        writeln!(out, "{} {}", max.x - min.x + 1, max.y - min.y + 1)?;
+        writeln!(out, "255")?;
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                out.write_all(&self.get_color(&Point::new(x, y)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Io for Canvas {
+    fn read(&mut self) -> Option<i64> {
+        None
+    }
+
+    fn write(&mut self, value: i64) {
+        self.feed(value);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Robot {
+    pub canvas: Canvas,
+    pub position: Point,
+    direction: Point,
+    painted: bool,
+}
+
+impl Robot {
+    pub fn new(canvas: Canvas) -> Robot {
+        Robot {
+            canvas,
+            position: Point::new(0, 0),
+            direction: Point::new(0, -1),
+            painted: false,
+        }
+    }
+
+    fn turn(&mut self, value: i64) {
+        let Point { x, y } = self.direction;
+
+        self.direction = match value {
+            0 => Point::new(y, -x),
+            _ => Point::new(-y, x),
+        };
+        self.position = Point::new(self.position.x + self.direction.x, self.position.y + self.direction.y);
+    }
+}
+
+impl Io for Robot {
+    fn read(&mut self) -> Option<i64> {
+        Some(self.canvas.get(&self.position).unwrap_or(0))
+    }
+
+    fn write(&mut self, value: i64) {
+        if self.painted {
+            self.turn(value);
+        } else {
+            self.canvas.paint(self.position, value);
+        }
+
+        self.painted = !self.painted;
+    }
+}
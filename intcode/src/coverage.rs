@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::cell::Cell;
+use crate::disasm::{decode_with, Line};
+use crate::isa::InstructionSet;
+use crate::observer::{Event, Observer};
+
+const HTML_HEADER: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Intcode coverage</title>
+<style>
+body { font-family: monospace; }
+table { border-collapse: collapse; }
+td { padding: 0 0.75em; white-space: pre; }
+tr.hit { background: #d4f7d4; }
+tr.miss { background: #f7d4d4; }
+tr.partial { background: #f7f0c4; }
+</style>
+</head>
+<body>
+";
+
+#[derive(Clone, Debug, Default)]
+pub struct Coverage {
+    instructions: Arc<InstructionSet>,
+    hits: BTreeMap<usize, u64>,
+    widths: BTreeMap<usize, usize>,
+    branches: BTreeMap<usize, (u64, u64)>,
+}
+
+enum Mark {
+    Hit,
+    Miss,
+    Partial,
+    Data,
+}
+
+impl Coverage {
+    pub fn new() -> Coverage {
+        Coverage::default()
+    }
+
+    pub fn with_instruction_set(instructions: Arc<InstructionSet>) -> Coverage {
+        Coverage { instructions, ..Coverage::default() }
+    }
+
+    pub fn hits(&self, address: usize) -> u64 {
+        self.hits.get(&address).cloned().unwrap_or(0)
+    }
+
+    pub fn branch(&self, address: usize) -> Option<(u64, u64)> {
+        self.branches.get(&address).cloned()
+    }
+
+    pub fn listing(&self, image: &[i64]) -> Vec<Line> {
+        let mut lines = vec![];
+        let mut address = 0;
+
+        while address < image.len() {
+            let line = decode_with(&self.instructions, &|address| image.get(address).cloned(), address).filter(|line| {
+                (address + 1..address + line.width()).all(|inner| !self.hits.contains_key(&inner))
+            });
+
+            match line {
+                Some(line) => {
+                    address += line.width();
+                    lines.push(line);
+                },
+                None => {
+                    let width = self.widths.get(&address).cloned().unwrap_or(1);
+
+                    let words = image.iter().enumerate().skip(address).take(width);
+
+                    lines.extend(words.map(|(address, value)| Line::Data { address, value: *value }));
+
+                    address += width;
+                },
+            }
+        }
+
+        lines
+    }
+
+    fn annotate(&self, line: &Line) -> (Mark, String, String) {
+        let hits = self.hits(line.address());
+        let branch = match line {
+            Line::Instruction { opcode: 5, .. } | Line::Instruction { opcode: 6, .. } => {
+                Some(self.branch(line.address()).unwrap_or((0, 0)))
+            },
+            _ => None,
+        };
+
+        let mark = match (line, hits, branch) {
+            (Line::Data { .. }, 0, _) => Mark::Data,
+            (_, 0, _) => Mark::Miss,
+            (_, _, Some((0, _))) | (_, _, Some((_, 0))) => Mark::Partial,
+            _ => Mark::Hit,
+        };
+        let count = match mark {
+            Mark::Data => String::new(),
+            Mark::Miss => String::from("-"),
+            _ => hits.to_string(),
+        };
+        let branch = match branch {
+            Some((taken, not_taken)) => format!("T:{} F:{}", taken, not_taken),
+            None => String::new(),
+        };
+
+        (mark, count, branch)
+    }
+
+    fn summary(&self, lines: &[Line]) -> Vec<String> {
+        let instructions: Vec<&Line> = lines.iter().filter(|line| matches!(line, Line::Instruction { .. })).collect();
+        let executed = instructions.iter().filter(|line| self.hits.contains_key(&line.address())).count();
+        let branches = instructions.iter().filter(|line| matches!(line, Line::Instruction { opcode: 5, .. } | Line::Instruction { opcode: 6, .. }));
+        let (directions, covered) = branches.fold((0, 0), |(directions, covered), line| {
+            let (taken, not_taken) = self.branch(line.address()).unwrap_or((0, 0));
+
+            (directions + 2, covered + (taken > 0) as usize + (not_taken > 0) as usize)
+        });
+        let percent = |part: usize, whole: usize| if whole == 0 { 100.0 } else { 100.0 * part as f64 / whole as f64 };
+
+        vec![
+            format!("instructions :: {} of {} executed ({:.1}%)", executed, instructions.len(), percent(executed, instructions.len())),
+            format!("branch directions :: {} of {} taken ({:.1}%)", covered, directions, percent(covered, directions)),
+        ]
+    }
+
+    pub fn report(&self, image: &[i64]) -> String {
+        let lines = self.listing(image);
+        let mut report = String::new();
+
+        for line in lines.iter() {
+            let (_, count, branch) = self.annotate(line);
+
+            report.push_str(format!("{:>8}  {:<12}{}", count, branch, line).trim_end());
+            report.push('\n');
+        }
+
+        for line in self.summary(&lines) {
+            report.push_str(&line);
+            report.push('\n');
+        }
+
+        report
+    }
+
+    pub fn write_html(&self, image: &[i64], out: &mut dyn Write) -> io::Result<()> {
+        let lines = self.listing(image);
+        let escape = |text: &str| text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+
+        write!(out, "{}", HTML_HEADER)?;
+
+        for line in self.summary(&lines) {
+            writeln!(out, "<p>{}</p>", escape(&line))?;
+        }
+
+        writeln!(out, "<table>")?;
+        writeln!(out, "<tr><th>hits</th><th>branch</th><th>instruction</th></tr>")?;
+
+        for line in lines.iter() {
+            let (mark, count, branch) = self.annotate(line);
+            let class = match mark {
+                Mark::Hit => " class=\"hit\"",
+                Mark::Miss => " class=\"miss\"",
+                Mark::Partial => " class=\"partial\"",
+                Mark::Data => "",
+            };
+
+            writeln!(out, "<tr{}><td>{}</td><td>{}</td><td>{}</td></tr>", class, count, branch, escape(line.to_string().trim_end()))?;
+        }
+
+        writeln!(out, "</table>")?;
+        writeln!(out, "</body>")?;
+        writeln!(out, "</html>")
+    }
+}
+
+impl<C: Cell> Observer<C> for Coverage {
+    fn observe(&mut self, event: &Event<C>) {
+        *self.hits.entry(event.ip).or_insert(0) += 1;
+        self.widths.insert(event.ip, event.operands.len() + 1);
+
+        if event.opcode == 5 || event.opcode == 6 {
+            let condition = event.reads.first().is_some_and(|read| !read.value.is_zero());
+            let branch = self.branches.entry(event.ip).or_insert((0, 0));
+
+            if condition == (event.opcode == 5) {
+                branch.0 += 1;
+            } else {
+                branch.1 += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, Write};
+
+use crate::disasm::{decode_with, Line};
+use crate::history::History;
+use crate::io::Queue;
+use crate::machine::{Machine, Status};
+use crate::snapshot::Snapshot;
+
+const CHECKPOINT_INTERVAL: usize = 1000;
+const CHECKPOINT_LIMIT: usize = 256;
+const DUMP_LIMIT: usize = 4096;
+
+const HELP: &str = "\
+commands:
+  s, step [n]          execute n instructions (default 1)
+  c, continue          run until a breakpoint, watch, input request or halt
+  rs, reverse-step [n] undo n instructions (default 1)
+  rc, reverse-continue [addr]
+                       run backwards to a breakpoint, a watch, or the last
+                       write to addr
+  history [n] [k]      show recorded steps, or checkpoint every n steps
+                       keeping k checkpoints (clears the history)
+  b, break <addr>      set a breakpoint
+  d, delete <addr>     remove a breakpoint
+  w, watch <addr>      stop when a memory cell changes
+  u, unwatch <addr>    remove a watch
+  r, regs              print ip, relative base and status
+  l, list [n]          disassemble n instructions from ip (default 5)
+  x, dump <addr> [n]   print n memory cells from addr (default 8)
+  p, poke <addr> <v>   write v to a memory cell
+  i, input <v>...      queue values for the program to read
+  save <file>          write the machine state to a snapshot file
+  load <file>          restore the machine state from a snapshot file
+  h, help              print this message
+  q, quit              leave the debugger";
+
+enum Stop {
+    Step,
+    Breakpoint,
+    Watch(usize, i64, i64),
+    NeedsInput,
+    Halted,
+    Error(String),
+    Start,
+    Write(usize, i64, i64),
+}
+
+pub struct Debugger {
+    machine: Machine,
+    queue: Queue,
+    breakpoints: BTreeSet<usize>,
+    watches: BTreeMap<usize, i64>,
+    history: History,
+}
+
+fn parse_number<T: std::str::FromStr>(word: Option<&&str>) -> Option<T> {
+    word.and_then(|word| word.parse().ok())
+}
+
+impl Debugger {
+    pub fn new(machine: Machine) -> Debugger {
+        Debugger {
+            machine,
+            queue: Queue::default(),
+            breakpoints: BTreeSet::new(),
+            watches: BTreeMap::new(),
+            history: History::new(CHECKPOINT_INTERVAL, CHECKPOINT_LIMIT),
+        }
+    }
+
+    pub fn machine(&self) -> &Machine {
+        &self.machine
+    }
+
+    fn decode(&self, address: usize) -> Option<Line> {
+        let memory = self.machine.memory();
+
+        decode_with(self.machine.instruction_set(), &|address| if address < memory.len() { Some(memory.get(address)) } else { None }, address)
+    }
+
+    fn current(&self) -> String {
+        match self.decode(self.machine.ip()) {
+            Some(line) => line.to_string(),
+            None => format!("{:>6}  DATA {}", self.machine.ip(), self.machine.read(self.machine.ip())),
+        }
+    }
+
+    fn single_step(&mut self, out: &mut dyn Write) -> io::Result<Option<Stop>> {
+        let status = match self.history.step(&mut self.machine, &mut self.queue) {
+            Ok(status) => status,
+            Err(e) => return Ok(Some(Stop::Error(e.to_string()))),
+        };
+
+        for value in self.queue.take_output() {
+            writeln!(out, "output: {}", value)?;
+        }
+
+        if let Some(stop) = self.check_watches() {
+            return Ok(Some(stop));
+        }
+
+        Ok(match status {
+            Status::NeedsInput => Some(Stop::NeedsInput),
+            Status::Halted => Some(Stop::Halted),
+            _ => None,
+        })
+    }
+
+    fn check_watches(&mut self) -> Option<Stop> {
+        for (address, last) in self.watches.iter_mut() {
+            let value = self.machine.read(*address);
+
+            if value != *last {
+                let old = *last;
+
+                *last = value;
+                return Some(Stop::Watch(*address, old, value));
+            }
+        }
+
+        None
+    }
+
+    fn single_undo(&mut self, target: Option<usize>) -> Option<Stop> {
+        let entry = match self.history.undo(&mut self.machine, &mut self.queue) {
+            Some(entry) => entry,
+            None => return Some(Stop::Start),
+        };
+
+        match entry.writes.iter().find(|write| Some(write.address) == target) {
+            Some(write) => {
+                self.check_watches();
+                Some(Stop::Write(write.address, write.old, write.value))
+            },
+            _ => self.check_watches(),
+        }
+    }
+
+    fn reverse_step(&mut self, count: usize) -> Stop {
+        for _ in 0..count {
+            if let Some(stop) = self.single_undo(None) {
+                return stop;
+            }
+        }
+
+        Stop::Step
+    }
+
+    fn reverse_continue(&mut self, target: Option<usize>) -> Stop {
+        loop {
+            if let Some(stop) = self.single_undo(target) {
+                return stop;
+            }
+
+            if self.breakpoints.contains(&self.machine.ip()) {
+                return Stop::Breakpoint;
+            }
+        }
+    }
+
+    fn step(&mut self, count: usize, out: &mut dyn Write) -> io::Result<Stop> {
+        for _ in 0..count {
+            if let Some(stop) = self.single_step(out)? {
+                return Ok(stop);
+            }
+        }
+
+        Ok(Stop::Step)
+    }
+
+    fn resume(&mut self, out: &mut dyn Write) -> io::Result<Stop> {
+        loop {
+            if let Some(stop) = self.single_step(out)? {
+                return Ok(stop);
+            }
+
+            if self.breakpoints.contains(&self.machine.ip()) {
+                return Ok(Stop::Breakpoint);
+            }
+        }
+    }
+
+    fn report(&self, stop: Stop, out: &mut dyn Write) -> io::Result<()> {
+        match stop {
+            Stop::Step => {},
+            Stop::Breakpoint => writeln!(out, "breakpoint at {}", self.machine.ip())?,
+            Stop::Watch(address, old, new) => writeln!(out, "watch [{}]: {} -> {}", address, old, new)?,
+            Stop::NeedsInput => writeln!(out, "waiting for input")?,
+            Stop::Halted => return writeln!(out, "halted"),
+            Stop::Error(e) => return writeln!(out, "error: {}", e),
+            Stop::Start => writeln!(out, "reached the start of the recorded history (step {})", self.history.steps())?,
+            Stop::Write(address, old, new) => writeln!(out, "last write to [{}]: {} -> {}", address, old, new)?,
+        }
+
+        writeln!(out, "{}", self.current())
+    }
+
+    fn print_registers(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "ip = {}", self.machine.ip())?;
+        writeln!(out, "rb = {}", self.machine.relative_base())?;
+        writeln!(out, "status = {:?}", self.machine.status())?;
+        writeln!(out, "breakpoints = {:?}", self.breakpoints)?;
+        writeln!(out, "watches = {:?}", self.watches.keys().collect::<Vec<_>>())
+    }
+
+    fn list(&self, count: usize, out: &mut dyn Write) -> io::Result<()> {
+        let mut address = self.machine.ip();
+
+        for _ in 0..count {
+            if address >= self.machine.memory().len() {
+                break;
+            }
+
+            match self.decode(address) {
+                Some(line) => {
+                    writeln!(out, "{}", line)?;
+                    address += line.width();
+                },
+                None => {
+                    writeln!(out, "{:>6}  DATA {}", address, self.machine.read(address))?;
+                    address += 1;
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn dump(&self, start: usize, count: usize, out: &mut dyn Write) -> io::Result<()> {
+        if count > DUMP_LIMIT {
+            return writeln!(out, "error: can dump at most {} cells", DUMP_LIMIT);
+        }
+
+        let end = match start.checked_add(count) {
+            Some(end) => end,
+            None => return writeln!(out, "error: address range overflows"),
+        };
+
+        for (offset, chunk) in self.machine.memory().range(start, end).chunks(8).enumerate() {
+            let words: Vec<String> = chunk.iter().map(|word| word.to_string()).collect();
+
+            writeln!(out, "{:>6}: {}", start + offset * 8, words.join(" "))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn execute(&mut self, command: &str, out: &mut dyn Write) -> io::Result<bool> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+
+        match words.first().cloned() {
+            None => {},
+            Some("s") | Some("step") => {
+                let stop = self.step(parse_number(words.get(1)).unwrap_or(1), out)?;
+
+                self.report(stop, out)?;
+            },
+            Some("c") | Some("continue") => {
+                let stop = self.resume(out)?;
+
+                self.report(stop, out)?;
+            },
+            Some("rs") | Some("reverse-step") => {
+                let stop = self.reverse_step(parse_number(words.get(1)).unwrap_or(1));
+
+                self.report(stop, out)?;
+            },
+            Some("rc") | Some("reverse-continue") => {
+                let stop = self.reverse_continue(parse_number(words.get(1)));
+
+                self.report(stop, out)?;
+            },
+            Some("history") => match (parse_number(words.get(1)), parse_number(words.get(2))) {
+                (Some(interval), limit) => {
+                    self.history = History::new(interval, limit.unwrap_or(CHECKPOINT_LIMIT));
+                },
+                _ => writeln!(out, "step {} (can rewind to step {})", self.history.steps(), self.history.earliest())?,
+            },
+            Some("b") | Some("break") => match parse_number(words.get(1)) {
+                Some(address) => {
+                    self.breakpoints.insert(address);
+                },
+                None => writeln!(out, "usage: break <addr>")?,
+            },
+            Some("d") | Some("delete") => match parse_number(words.get(1)) {
+                Some(address) => {
+                    self.breakpoints.remove(&address);
+                },
+                None => writeln!(out, "usage: delete <addr>")?,
+            },
+            Some("w") | Some("watch") => match parse_number(words.get(1)) {
+                Some(address) => {
+                    self.watches.insert(address, self.machine.read(address));
+                },
+                None => writeln!(out, "usage: watch <addr>")?,
+            },
+            Some("u") | Some("unwatch") => match parse_number(words.get(1)) {
+                Some(address) => {
+                    self.watches.remove(&address);
+                },
+                None => writeln!(out, "usage: unwatch <addr>")?,
+            },
+            Some("r") | Some("regs") => self.print_registers(out)?,
+            Some("l") | Some("list") => self.list(parse_number(words.get(1)).unwrap_or(5), out)?,
+            Some("x") | Some("dump") => match parse_number(words.get(1)) {
+                Some(address) => self.dump(address, parse_number(words.get(2)).unwrap_or(8), out)?,
+                None => writeln!(out, "usage: dump <addr> [n]")?,
+            },
+            Some("p") | Some("poke") => match (parse_number(words.get(1)), parse_number(words.get(2))) {
+                (Some(address), Some(value)) => {
+                    self.machine.write(address, value);
+                    self.history.clear();
+
+                    if self.watches.contains_key(&address) {
+                        self.watches.insert(address, value);
+                    }
+                },
+                _ => writeln!(out, "usage: poke <addr> <value>")?,
+            },
+            Some("i") | Some("input") => {
+                for word in &words[1..] {
+                    match word.parse() {
+                        Ok(value) => self.queue.push(value),
+                        Err(_) => writeln!(out, "not a number: {}", word)?,
+                    }
+                }
+            },
+            Some("save") => match words.get(1) {
+                Some(file_path) => {
+                    let snapshot = Snapshot::new(self.machine.clone(), self.queue.clone());
+
+                    match snapshot.save(file_path) {
+                        Ok(()) => writeln!(out, "saved {}", file_path)?,
+                        Err(e) => writeln!(out, "error: {}", e)?,
+                    }
+                },
+                None => writeln!(out, "usage: save <file>")?,
+            },
+            Some("load") => match words.get(1) {
+                Some(file_path) => match Snapshot::load(file_path, self.machine.instruction_set().clone()) {
+                    Ok(snapshot) => {
+                        self.machine = snapshot.machine;
+                        self.history.clear();
+                        self.queue = snapshot.queue;
+
+                        for (address, value) in self.watches.iter_mut() {
+                            *value = self.machine.read(*address);
+                        }
+
+                        writeln!(out, "{}", self.current())?;
+                    },
+                    Err(e) => writeln!(out, "error: {}", e)?,
+                },
+                None => writeln!(out, "usage: load <file>")?,
+            },
+            Some("h") | Some("help") => writeln!(out, "{}", HELP)?,
+            Some("q") | Some("quit") => return Ok(false),
+            Some(other) => writeln!(out, "unknown command '{}', try 'help'", other)?,
+        }
+
+        Ok(true)
+    }
+
+    pub fn repl(&mut self, input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", self.current())?;
+
+        loop {
+            write!(out, "(debug) ")?;
+            out.flush()?;
+
+            let mut command = String::new();
+
+            if input.read_line(&mut command)? == 0 || !self.execute(command.trim(), out)? {
+                return Ok(());
+            }
+        }
+    }
+}
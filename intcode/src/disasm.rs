@@ -0,0 +1,132 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::cell::Cell;
+use crate::isa::{get_standard_set, InstructionSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Operand {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Line {
+    Instruction {
+        address: usize,
+        opcode: i64,
+        mnemonic: &'static str,
+        operands: Vec<Operand>,
+    },
+    Data {
+        address: usize,
+        value: i64,
+    },
+}
+
+fn get_mode(instruction: i64, n: usize) -> Option<Mode> {
+    match (instruction / 10_i64.pow(n as u32 + 1)) % 10 {
+        0 => Some(Mode::Position),
+        1 => Some(Mode::Immediate),
+        2 => Some(Mode::Relative),
+        _ => None,
+    }
+}
+
+pub fn decode_with<C: Cell>(instructions: &InstructionSet<C>, fetch: &dyn Fn(usize) -> Option<i64>, address: usize) -> Option<Line> {
+    let instruction = fetch(address)?;
+
+    if instruction < 0 {
+        return None;
+    }
+
+    let opcode = instruction % 100;
+    let (mnemonic, arity) = instructions.get(opcode).map(|instruction| (instruction.mnemonic, instruction.arity))?;
+
+    if 10_i64.checked_pow(arity as u32 + 2).is_some_and(|limit| instruction >= limit) {
+        return None;
+    }
+
+    let mut operands = vec![];
+
+    for n in 1..=arity {
+        operands.push(Operand {
+            mode: get_mode(instruction, n)?,
+            value: fetch(address + n)?,
+        });
+    }
+
+    Some(Line::Instruction { address, opcode, mnemonic, operands })
+}
+
+pub fn decode(memory: &[i64], address: usize) -> Option<Line> {
+    decode_with(get_standard_set(), &|address| memory.get(address).cloned(), address)
+}
+
+pub fn disassemble(memory: &[i64]) -> Vec<Line> {
+    disassemble_with(get_standard_set(), memory)
+}
+
+pub fn disassemble_with<C: Cell>(instructions: &InstructionSet<C>, memory: &[i64]) -> Vec<Line> {
+    let mut lines = vec![];
+    let mut address = 0;
+
+    while address < memory.len() {
+        let line = decode_with(instructions, &|address| memory.get(address).cloned(), address).unwrap_or(Line::Data {
+            address,
+            value: memory[address],
+        });
+
+        address += line.width();
+        lines.push(line);
+    }
+
+    lines
+}
+
+impl Line {
+    pub fn address(&self) -> usize {
+        match self {
+            Line::Instruction { address, .. } | Line::Data { address, .. } => *address,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        match self {
+            Line::Instruction { operands, .. } => operands.len() + 1,
+            Line::Data { .. } => 1,
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "#{}", self.value),
+            Mode::Relative if self.value < 0 => write!(f, "rb{}", self.value),
+            Mode::Relative => write!(f, "rb+{}", self.value),
+        }
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Instruction { address, mnemonic, operands, .. } => {
+                let operands: Vec<String> = operands.iter().map(|operand| operand.to_string()).collect();
+
+                write!(f, "{:>6}  {:<4} {}", address, mnemonic, operands.join(", "))
+            },
+            Line::Data { address, value } => write!(f, "{:>6}  DATA {}", address, value),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::cell::Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnknownOpcode,
+    UnknownMode(i64),
+    NegativeAddress(i64),
+    AddressOutOfRange,
+    IpOutOfBounds,
+    Overflow,
+    InvalidParameter(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VmError<C = i64> {
+    pub ip: usize,
+    pub instruction: C,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnknownOpcode => write!(f, "unknown opcode"),
+            ErrorKind::UnknownMode(mode) => write!(f, "unknown parameter mode {}", mode),
+            ErrorKind::NegativeAddress(address) => write!(f, "negative address {}", address),
+            ErrorKind::AddressOutOfRange => write!(f, "address out of range"),
+            ErrorKind::IpOutOfBounds => write!(f, "instruction pointer out of bounds"),
+            ErrorKind::Overflow => write!(f, "arithmetic overflow"),
+            ErrorKind::InvalidParameter(n) => write!(f, "instruction has no parameter {}", n),
+        }
+    }
+}
+
+impl<C: Cell> fmt::Display for VmError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at ip {} (instruction {})", self.kind, self.ip, self.instruction)
+    }
+}
+
+impl<C: Cell> Error for VmError<C> {}
@@ -0,0 +1,360 @@
+use std::mem;
+
+use crate::error::VmError;
+use crate::io::{Io, Queue};
+use crate::machine::{get_nth_parameter_mode, Machine, Status};
+use crate::memory::Memory;
+
+const MAX_WIDTH: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Param {
+    Position(usize),
+    Immediate(i64),
+    Relative(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Add(Param, Param, Param),
+    Multiply(Param, Param, Param),
+    Input(Param),
+    Output(Param),
+    JumpIfTrue(Param, Param),
+    JumpIfFalse(Param, Param),
+    LessThan(Param, Param, Param),
+    Equals(Param, Param, Param),
+    AdjustBase(Param),
+    Halt,
+}
+
+impl Op {
+    fn width(&self) -> usize {
+        match self {
+            Op::Add(..) | Op::Multiply(..) | Op::LessThan(..) | Op::Equals(..) => 4,
+            Op::JumpIfTrue(..) | Op::JumpIfFalse(..) => 3,
+            Op::Input(_) | Op::Output(_) | Op::AdjustBase(_) => 2,
+            Op::Halt => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Compiled {
+    image: Vec<i64>,
+    ops: Vec<Option<Op>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FastMachine<'a> {
+    program: &'a Compiled,
+    memory: Memory,
+    ip: usize,
+    relative_base: i64,
+    status: Status,
+    stale: Vec<bool>,
+    fallback: Option<Machine>,
+}
+
+fn decode_param(memory: &Memory, address: usize, n: usize, dest: bool) -> Option<Param> {
+    let word = memory.get(address + n);
+
+    match get_nth_parameter_mode(memory.get(address), n) {
+        0 if word >= 0 => Some(Param::Position(word as usize)),
+        1 if dest => Some(Param::Position(address + n)),
+        1 => Some(Param::Immediate(word)),
+        2 => Some(Param::Relative(word)),
+        _ => None,
+    }
+}
+
+fn decode(memory: &Memory, address: usize) -> Option<Op> {
+    let read = |n| decode_param(memory, address, n, false);
+    let dest = |n| decode_param(memory, address, n, true);
+
+    let op = match memory.get(address) % 100 {
+        1 => Op::Add(read(1)?, read(2)?, dest(3)?),
+        2 => Op::Multiply(read(1)?, read(2)?, dest(3)?),
+        3 => Op::Input(dest(1)?),
+        4 => Op::Output(read(1)?),
+        5 => Op::JumpIfTrue(read(1)?, read(2)?),
+        6 => Op::JumpIfFalse(read(1)?, read(2)?),
+        7 => Op::LessThan(read(1)?, read(2)?, dest(3)?),
+        8 => Op::Equals(read(1)?, read(2)?, dest(3)?),
+        9 => Op::AdjustBase(read(1)?),
+        99 => Op::Halt,
+        _ => return None,
+    };
+
+    if address + op.width() > memory.len() {
+        return None;
+    }
+
+    Some(op)
+}
+
+impl Compiled {
+    pub fn new(image: &[i64]) -> Compiled {
+        let memory = Memory::new(image.to_vec());
+        let ops = (0..image.len()).map(|address| decode(&memory, address)).collect();
+
+        Compiled { image: image.to_vec(), ops }
+    }
+
+    pub fn machine(&self) -> FastMachine<'_> {
+        FastMachine::new(self)
+    }
+}
+
+impl<'a> FastMachine<'a> {
+    pub fn new(program: &'a Compiled) -> FastMachine<'a> {
+        FastMachine {
+            program,
+            memory: Memory::new(program.image.clone()),
+            ip: 0,
+            relative_base: 0,
+            status: Status::Running,
+            stale: vec![false; program.image.len()],
+            fallback: None,
+        }
+    }
+
+    pub fn memory(&self) -> &Memory {
+        match &self.fallback {
+            Some(machine) => machine.memory(),
+            None => &self.memory,
+        }
+    }
+
+    pub fn ip(&self) -> usize {
+        self.fallback.as_ref().map_or(self.ip, |machine| machine.ip())
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.fallback.as_ref().map_or(self.relative_base, |machine| machine.relative_base())
+    }
+
+    pub fn status(&self) -> &Status {
+        match &self.fallback {
+            Some(machine) => machine.status(),
+            None => &self.status,
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        *self.status() == Status::Halted
+    }
+
+    pub fn is_interpreted(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.memory().get(address)
+    }
+
+    pub fn write(&mut self, address: usize, value: i64) {
+        match &mut self.fallback {
+            Some(machine) => machine.write(address, value),
+            None => self.store(address, value),
+        }
+    }
+
+    pub fn into_machine(mut self) -> Machine {
+        self.interpreter();
+        self.fallback.unwrap()
+    }
+
+    fn interpreter(&mut self) -> &mut Machine {
+        if self.fallback.is_none() {
+            let memory = mem::take(&mut self.memory);
+
+            self.fallback = Some(Machine::from_parts(memory, self.ip, self.relative_base, self.status.clone()));
+            self.stale = vec![];
+        }
+
+        self.fallback.as_mut().unwrap()
+    }
+
+    fn fetch(&self) -> Option<Op> {
+        match self.stale.get(self.ip) {
+            Some(false) => self.program.ops[self.ip],
+            _ => decode(&self.memory, self.ip),
+        }
+    }
+
+    fn address(&self, param: Param) -> Option<usize> {
+        match param {
+            Param::Position(address) => Some(address),
+            Param::Immediate(_) => None,
+            Param::Relative(offset) => match self.relative_base.checked_add(offset)? {
+                address if address >= 0 => Some(address as usize),
+                _ => None,
+            },
+        }
+    }
+
+    fn load(&self, param: Param) -> Option<i64> {
+        match param {
+            Param::Immediate(value) => Some(value),
+            param => Some(self.memory.get(self.address(param)?)),
+        }
+    }
+
+    fn store(&mut self, address: usize, value: i64) {
+        let first = address.saturating_sub(MAX_WIDTH - 1);
+        let end = usize::min(address + 1, self.stale.len());
+
+        if first < end {
+            self.stale[first..end].fill(true);
+        }
+
+        self.memory.set(address, value);
+    }
+
+    fn arithmetic(&mut self, left: Param, right: Param, dest: Param, operation: fn(i64, i64) -> Option<i64>) -> Option<()> {
+        let result = operation(self.load(left)?, self.load(right)?)?;
+        let dest = self.address(dest)?;
+
+        self.store(dest, result);
+        self.ip += 4;
+
+        Some(())
+    }
+
+    fn jump(&mut self, test: Param, target: Param, condition: bool) -> Option<()> {
+        if (self.load(test)? != 0) == condition {
+            match self.load(target)? {
+                target if target >= 0 => self.ip = target as usize,
+                _ => return None,
+            }
+        } else {
+            self.ip += 3;
+        }
+
+        Some(())
+    }
+
+    fn execute(&mut self, io: &mut dyn Io) -> Option<()> {
+        match self.fetch()? {
+            Op::Add(left, right, dest) => self.arithmetic(left, right, dest, i64::checked_add)?,
+            Op::Multiply(left, right, dest) => self.arithmetic(left, right, dest, i64::checked_mul)?,
+            Op::Input(dest) => {
+                let dest = self.address(dest)?;
+
+                match io.read() {
+                    Some(value) => {
+                        self.store(dest, value);
+                        self.ip += 2;
+                    },
+                    None => self.status = Status::NeedsInput,
+                }
+            },
+            Op::Output(value) => {
+                let value = self.load(value)?;
+
+                io.write(value);
+                self.status = Status::Output(value);
+                self.ip += 2;
+            },
+            Op::JumpIfTrue(test, target) => self.jump(test, target, true)?,
+            Op::JumpIfFalse(test, target) => self.jump(test, target, false)?,
+            Op::LessThan(left, right, dest) => self.arithmetic(left, right, dest, |left, right| Some((left < right) as i64))?,
+            Op::Equals(left, right, dest) => self.arithmetic(left, right, dest, |left, right| Some((left == right) as i64))?,
+            Op::AdjustBase(offset) => {
+                self.relative_base = self.relative_base.checked_add(self.load(offset)?)?;
+                self.ip += 2;
+            },
+            Op::Halt => self.status = Status::Halted,
+        };
+
+        Some(())
+    }
+
+    pub fn step(&mut self, io: &mut dyn Io) -> Result<Status, VmError> {
+        if let Some(machine) = &mut self.fallback {
+            return machine.step(io);
+        }
+
+        if self.status == Status::Halted {
+            return Ok(Status::Halted);
+        }
+
+        self.status = Status::Running;
+
+        if self.execute(io).is_none() {
+            return self.interpreter().step(io);
+        }
+
+        Ok(self.status.clone())
+    }
+
+    fn drive(&mut self, io: &mut dyn Io, pause_on_output: bool, fuel: &mut usize) -> Option<Status> {
+        if self.fallback.is_some() {
+            return None;
+        }
+
+        while self.status != Status::Halted {
+            if *fuel == 0 {
+                return Some(Status::Running);
+            }
+
+            self.status = Status::Running;
+            self.execute(io)?;
+            *fuel -= 1;
+
+            match self.status {
+                Status::Running => continue,
+                Status::Output(_) if !pause_on_output => continue,
+                _ => break,
+            }
+        }
+
+        Some(self.status.clone())
+    }
+
+    pub fn resume(&mut self, io: &mut dyn Io) -> Result<Status, VmError> {
+        let mut fuel = usize::MAX;
+
+        match self.drive(io, true, &mut fuel) {
+            Some(status) => Ok(status),
+            None => self.interpreter().resume(io),
+        }
+    }
+
+    pub fn run(&mut self, io: &mut dyn Io) -> Result<Status, VmError> {
+        let mut fuel = usize::MAX;
+
+        match self.drive(io, false, &mut fuel) {
+            Some(status) => Ok(status),
+            None => self.interpreter().run(io),
+        }
+    }
+
+    pub fn run_with_fuel(&mut self, io: &mut dyn Io, fuel: usize) -> Result<Status, VmError> {
+        let mut fuel = fuel;
+
+        if let Some(status) = self.drive(io, false, &mut fuel) {
+            return Ok(status);
+        }
+
+        let machine = self.interpreter();
+
+        for _ in 0..fuel {
+            match machine.step(io)? {
+                Status::Running | Status::Output(_) => continue,
+                status => return Ok(status),
+            }
+        }
+
+        Ok(Status::Running)
+    }
+
+    pub fn run_with_input(&mut self, input: Vec<i64>) -> Result<Vec<i64>, VmError> {
+        let mut queue = Queue::new(input);
+
+        self.run(&mut queue)?;
+
+        Ok(queue.into_output())
+    }
+}
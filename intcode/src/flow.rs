@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::cell::Cell;
+use crate::disasm::{decode_with, Line, Mode, Operand};
+use crate::isa::{get_standard_set, InstructionSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    Fallthrough,
+    Taken,
+    NotTaken,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    pub start: usize,
+    pub lines: Vec<Line>,
+    pub successors: Vec<(usize, Edge)>,
+    pub invalid: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Flow {
+    pub blocks: BTreeMap<usize, Block>,
+    pub self_modifying: Vec<(usize, usize)>,
+    pub indirect_jumps: Vec<usize>,
+    pub patched: Vec<usize>,
+    pub unreachable: Vec<(usize, usize)>,
+    pub unknown: Vec<(usize, usize)>,
+}
+
+fn is_branch(line: &Line) -> bool {
+    match line {
+        Line::Instruction { opcode, .. } => *opcode == 5 || *opcode == 6 || *opcode == 99,
+        Line::Data { .. } => true,
+    }
+}
+
+fn get_successors(line: &Line, indirect_jumps: &mut Vec<usize>) -> Vec<(usize, Edge)> {
+    let (address, opcode, operands) = match line {
+        Line::Instruction { address, opcode, operands, .. } => (*address, *opcode, operands),
+        Line::Data { .. } => return vec![],
+    };
+    let next = address + line.width();
+
+    match opcode {
+        99 => vec![],
+        5 | 6 => {
+            let condition = match operands[0] {
+                Operand { mode: Mode::Immediate, value } => Some((value != 0) == (opcode == 5)),
+                _ => None,
+            };
+            let target = match operands[1] {
+                Operand { mode: Mode::Immediate, value } if value >= 0 => Some(value as usize),
+                _ => None,
+            };
+
+            if target.is_none() && condition != Some(false) {
+                indirect_jumps.push(address);
+            }
+
+            let mut successors = vec![];
+
+            if let (Some(target), true) = (target, condition != Some(false)) {
+                successors.push((target, Edge::Taken));
+            }
+
+            if condition != Some(true) {
+                successors.push((next, Edge::NotTaken));
+            }
+
+            successors
+        },
+        _ => vec![(next, Edge::Fallthrough)],
+    }
+}
+
+fn explore<C: Cell>(instructions: &InstructionSet<C>, memory: &[i64], indirect_jumps: &mut Vec<usize>) -> BTreeMap<usize, (Line, Vec<(usize, Edge)>)> {
+    let mut reached = BTreeMap::new();
+    let mut pending = vec![0];
+
+    while let Some(address) = pending.pop() {
+        if reached.contains_key(&address) || address >= memory.len() {
+            continue;
+        }
+
+        let line = decode_with(instructions, &|address| memory.get(address).cloned(), address).unwrap_or(Line::Data { address, value: memory[address] });
+        let successors = get_successors(&line, indirect_jumps);
+
+        pending.extend(successors.iter().map(|(target, _)| *target));
+        reached.insert(address, (line, successors));
+    }
+
+    reached
+}
+
+pub fn analyze(memory: &[i64]) -> Flow {
+    analyze_with(get_standard_set(), memory)
+}
+
+pub fn analyze_with<C: Cell>(instructions: &InstructionSet<C>, memory: &[i64]) -> Flow {
+    let mut flow = Flow::default();
+    let reached = explore(instructions, memory, &mut flow.indirect_jumps);
+
+    let mut leaders: BTreeSet<usize> = BTreeSet::new();
+
+    leaders.insert(0);
+
+    for (line, successors) in reached.values() {
+        if is_branch(line) {
+            leaders.extend(successors.iter().map(|(target, _)| *target));
+        }
+
+        if let Line::Data { address, .. } = line {
+            leaders.insert(*address);
+        }
+    }
+
+    for leader in leaders.iter().filter(|leader| reached.contains_key(leader)) {
+        let mut block = Block { start: *leader, lines: vec![], successors: vec![], invalid: false };
+        let mut address = *leader;
+
+        while let Some((line, successors)) = reached.get(&address) {
+            block.lines.push(line.clone());
+            block.successors = successors.clone();
+            block.invalid = matches!(line, Line::Data { .. });
+            address += line.width();
+
+            if is_branch(line) || leaders.contains(&address) {
+                break;
+            }
+        }
+
+        flow.blocks.insert(*leader, block);
+    }
+
+    let mut code: BTreeSet<usize> = BTreeSet::new();
+
+    for (address, (line, _)) in reached.iter() {
+        code.extend(*address..*address + line.width());
+    }
+
+    for (address, (line, _)) in reached.iter() {
+        if let Line::Instruction { opcode, operands, .. } = line {
+            let writes = instructions.get(*opcode).map_or(&[][..], |instruction| &instruction.writes[..]);
+
+            for n in writes.iter() {
+                let target = match operands[n - 1] {
+                    Operand { mode: Mode::Position, value } if value >= 0 => value as usize,
+                    Operand { mode: Mode::Immediate, .. } => address + n,
+                    _ => continue,
+                };
+
+                if code.contains(&target) {
+                    flow.self_modifying.push((*address, target));
+                }
+            }
+        }
+    }
+
+    let targets: BTreeSet<usize> = flow.self_modifying.iter().map(|(_, target)| *target).collect();
+
+    for block in flow.blocks.values() {
+        if let Some(Line::Data { address, .. }) = block.lines.last() {
+            if targets.contains(address) {
+                flow.patched.push(*address);
+            }
+        }
+    }
+
+    let mut start = None;
+    let mut gaps = vec![];
+
+    for address in 0..=memory.len() {
+        let unreached = address < memory.len() && !code.contains(&address);
+
+        match (start, unreached) {
+            (None, true) => start = Some(address),
+            (Some(first), false) => {
+                gaps.push((first, address));
+                start = None;
+            },
+            _ => {},
+        }
+    }
+
+    if flow.patched.is_empty() {
+        flow.unreachable = gaps;
+    } else {
+        flow.unknown = gaps;
+    }
+
+    flow
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Flow {
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph intcode {\n    node [shape=box, fontname=\"monospace\"];\n");
+
+        for block in self.blocks.values() {
+            let label: String = block.lines.iter().map(|line| format!("{}\\l", escape(line.to_string().trim()))).collect();
+            let style = if block.invalid { ", color=red" } else { "" };
+
+            dot.push_str(&format!("    b{} [label=\"{}\"{}];\n", block.start, label, style));
+        }
+
+        for block in self.blocks.values() {
+            for (target, edge) in block.successors.iter() {
+                let label = match edge {
+                    Edge::Fallthrough => "",
+                    Edge::Taken => " [label=\"taken\"]",
+                    Edge::NotTaken => " [label=\"not taken\", style=dashed]",
+                };
+
+                dot.push_str(&format!("    b{} -> b{}{};\n", block.start, target, label));
+            }
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+impl fmt::Display for Flow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "basic blocks :: {}", self.blocks.len())?;
+
+        for block in self.blocks.values() {
+            let successors: Vec<String> = block.successors.iter().map(|(target, _)| target.to_string()).collect();
+            let kind = match block.lines.last() {
+                Some(Line::Data { address, .. }) if self.patched.contains(address) => " (patched at runtime)",
+                _ if block.invalid => " (undecodable)",
+                _ => "",
+            };
+
+            writeln!(f, "  block {}{} -> [{}]", block.start, kind, successors.join(", "))?;
+        }
+
+        writeln!(f, "self-modifying writes :: {}", self.self_modifying.len())?;
+
+        for (address, target) in self.self_modifying.iter() {
+            writeln!(f, "  {} writes code at [{}]", address, target)?;
+        }
+
+        writeln!(f, "indirect jumps :: {}", self.indirect_jumps.len())?;
+
+        for address in self.indirect_jumps.iter() {
+            writeln!(f, "  {}", address)?;
+        }
+
+        writeln!(f, "unreachable ranges :: {}", self.unreachable.len())?;
+
+        for (start, end) in self.unreachable.iter() {
+            writeln!(f, "  {}..{} ({} words)", start, end, end - start)?;
+        }
+
+        if !self.patched.is_empty() {
+            let patched: Vec<String> = self.patched.iter().map(|address| address.to_string()).collect();
+
+            writeln!(f, "unknown ranges (control reaches code patched at runtime: {}) :: {}", patched.join(", "), self.unknown.len())?;
+
+            for (start, end) in self.unknown.iter() {
+                writeln!(f, "  {}..{} ({} words)", start, end, end - start)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::error::VmError;
+use crate::fast::Compiled;
+use crate::isa::get_standard_set;
+use crate::io::Queue;
+use crate::machine::{Machine, Status};
+use crate::memory::Memory;
+
+const DATA_WORDS: usize = 8;
+
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed ^ 0x9e37_79b9_7f4a_7c15 }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        self.state
+    }
+
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next_u64() % (high - low) as u64) as i64
+    }
+
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub result: Result<Status, VmError>,
+    pub ip: usize,
+    pub relative_base: i64,
+    pub len: usize,
+    pub memory: BTreeMap<usize, i64>,
+    pub output: Vec<i64>,
+}
+
+pub trait Backend {
+    fn name(&self) -> &str;
+    fn execute(&self, program: &[i64], input: &[i64], fuel: usize) -> Outcome;
+}
+
+pub struct Interpreter;
+
+pub struct Precompiled;
+
+pub struct Driven;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Case {
+    pub program: Vec<i64>,
+    pub input: Vec<i64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Failure {
+    pub case: Case,
+    pub reference: Outcome,
+    pub candidate: Outcome,
+}
+
+fn get_outcome(result: Result<Status, VmError>, ip: usize, relative_base: i64, memory: &Memory, output: Vec<i64>) -> Outcome {
+    Outcome {
+        result,
+        ip,
+        relative_base,
+        len: memory.len(),
+        memory: memory.cells().filter(|(_, value)| **value != 0).map(|(address, value)| (address, *value)).collect(),
+        output,
+    }
+}
+
+fn run_with_fuel(fuel: usize, mut step: impl FnMut() -> Result<Status, VmError>) -> Result<Status, VmError> {
+    for _ in 0..fuel {
+        match step()? {
+            Status::Running | Status::Output(_) => continue,
+            status => return Ok(status),
+        }
+    }
+
+    Ok(Status::Running)
+}
+
+impl Backend for Interpreter {
+    fn name(&self) -> &str {
+        "interpreter"
+    }
+
+    fn execute(&self, program: &[i64], input: &[i64], fuel: usize) -> Outcome {
+        let mut machine = Machine::new(program.to_vec());
+        let mut queue = Queue::new(input.to_vec());
+        let result = run_with_fuel(fuel, || machine.step(&mut queue));
+
+        get_outcome(result, machine.ip(), machine.relative_base(), machine.memory(), queue.into_output())
+    }
+}
+
+impl Backend for Precompiled {
+    fn name(&self) -> &str {
+        "precompiled"
+    }
+
+    fn execute(&self, program: &[i64], input: &[i64], fuel: usize) -> Outcome {
+        let compiled = Compiled::new(program);
+        let mut machine = compiled.machine();
+        let mut queue = Queue::new(input.to_vec());
+        let result = run_with_fuel(fuel, || machine.step(&mut queue));
+
+        get_outcome(result, machine.ip(), machine.relative_base(), machine.memory(), queue.into_output())
+    }
+}
+
+impl Backend for Driven {
+    fn name(&self) -> &str {
+        "driven"
+    }
+
+    fn execute(&self, program: &[i64], input: &[i64], fuel: usize) -> Outcome {
+        let compiled = Compiled::new(program);
+        let mut machine = compiled.machine();
+        let mut queue = Queue::new(input.to_vec());
+        let result = machine.run_with_fuel(&mut queue, fuel);
+
+        get_outcome(result, machine.ip(), machine.relative_base(), machine.memory(), queue.into_output())
+    }
+}
+
+fn get_arity(opcode: i64) -> usize {
+    get_standard_set().get(opcode).map_or(0, |instruction| instruction.arity)
+}
+
+fn is_write(opcode: i64, n: usize) -> bool {
+    get_standard_set().get(opcode).is_some_and(|instruction| instruction.writes.contains(&n))
+}
+
+pub fn generate(rng: &mut Rng, instructions: usize) -> Case {
+    let choices: Vec<i64> = get_standard_set().opcodes().filter(|opcode| *opcode != 99).collect();
+    let mut opcodes: Vec<i64> = (0..instructions).map(|_| choices[rng.below(choices.len())]).collect();
+
+    opcodes.push(99);
+
+    let mut starts = vec![];
+    let mut code_len = 0;
+
+    for opcode in opcodes.iter() {
+        starts.push(code_len as i64);
+        code_len += get_arity(*opcode) + 1;
+    }
+
+    let total = (code_len + DATA_WORDS) as i64;
+    let mut program = vec![];
+
+    for opcode in opcodes {
+        let mut instruction = opcode;
+        let mut operands = vec![];
+
+        for n in 1..=get_arity(opcode) {
+            let jump_target = (opcode == 5 || opcode == 6) && n == 2;
+            let mode = match rng.below(10) {
+                0..=4 => 0,
+                5..=7 if !is_write(opcode, n) || rng.chance(10) => 1,
+                5..=7 => 0,
+                _ => 2,
+            };
+            let value = match mode {
+                1 if jump_target => starts[rng.below(starts.len())],
+                1 if opcode == 9 => rng.range(-4, 5),
+                1 => rng.range(-3, 10),
+                2 => rng.range(-2, total),
+                _ if rng.chance(2) => rng.range(-2, 0),
+                _ => rng.range(0, total + 4),
+            };
+
+            instruction += mode * 10_i64.pow(n as u32 + 1);
+            operands.push(value);
+        }
+
+        program.push(instruction);
+        program.extend(operands);
+    }
+
+    program.extend((0..DATA_WORDS).map(|_| if rng.chance(30) { starts[rng.below(starts.len())] } else { rng.range(-5, 20) }));
+
+    let input = (0..rng.below(6)).map(|_| rng.range(-10, 100)).collect();
+
+    Case { program, input }
+}
+
+pub fn check(case: &Case, reference: &dyn Backend, candidate: &dyn Backend, fuel: usize) -> Option<Failure> {
+    let expected = reference.execute(&case.program, &case.input, fuel);
+    let actual = candidate.execute(&case.program, &case.input, fuel);
+
+    if expected == actual {
+        return None;
+    }
+
+    Some(Failure { case: case.clone(), reference: expected, candidate: actual })
+}
+
+fn get_simplifications(case: &Case) -> Vec<Case> {
+    let mut simplifications = vec![];
+    let len = case.program.len();
+    let mut size = len / 2;
+
+    while size > 0 {
+        for start in (0..len).step_by(size) {
+            let mut program = case.program.clone();
+
+            program.drain(start..usize::min(start + size, len));
+            simplifications.push(Case { program, input: case.input.clone() });
+        }
+
+        size /= 2;
+    }
+
+    for (address, word) in case.program.iter().enumerate() {
+        let simpler = match *word {
+            0 => continue,
+            word if word.abs() >= 100 => word % 100,
+            word if word.abs() > 1 => word / 2,
+            _ => 0,
+        };
+        let mut program = case.program.clone();
+
+        program[address] = simpler;
+        simplifications.push(Case { program, input: case.input.clone() });
+    }
+
+    for index in 0..case.input.len() {
+        let mut input = case.input.clone();
+
+        input.remove(index);
+        simplifications.push(Case { program: case.program.clone(), input });
+    }
+
+    simplifications
+}
+
+pub fn shrink(failure: Failure, reference: &dyn Backend, candidate: &dyn Backend, fuel: usize) -> Failure {
+    let mut smallest = failure;
+
+    'search: loop {
+        for case in get_simplifications(&smallest.case) {
+            if let Some(failure) = check(&case, reference, candidate, fuel) {
+                smallest = failure;
+                continue 'search;
+            }
+        }
+
+        return smallest;
+    }
+}
+
+pub fn fuzz(seed: u64, iterations: usize, reference: &dyn Backend, candidate: &dyn Backend, fuel: usize) -> Option<Failure> {
+    let mut rng = Rng::new(seed);
+
+    for _ in 0..iterations {
+        let instructions = 1 + rng.below(24);
+        let case = generate(&mut rng, instructions);
+
+        if let Some(failure) = check(&case, reference, candidate, fuel) {
+            return Some(shrink(failure, reference, candidate, fuel));
+        }
+    }
+
+    None
+}
+
+fn describe(outcome: &Outcome) -> String {
+    match &outcome.result {
+        Ok(status) => format!("{:?}", status),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let words: Vec<String> = self.case.program.iter().map(|word| word.to_string()).collect();
+        let (expected, actual) = (&self.reference, &self.candidate);
+
+        writeln!(f, "program :: {}", words.join(","))?;
+        writeln!(f, "input :: {:?}", self.case.input)?;
+
+        if expected.result != actual.result {
+            writeln!(f, "result :: {} != {}", describe(expected), describe(actual))?;
+        }
+
+        if expected.output != actual.output {
+            writeln!(f, "output :: {:?} != {:?}", expected.output, actual.output)?;
+        }
+
+        if (expected.ip, expected.relative_base) != (actual.ip, actual.relative_base) {
+            writeln!(f, "registers :: ip {} rb {} != ip {} rb {}", expected.ip, expected.relative_base, actual.ip, actual.relative_base)?;
+        }
+
+        if expected.len != actual.len {
+            writeln!(f, "memory length :: {} != {}", expected.len, actual.len)?;
+        }
+
+        let addresses = expected.memory.keys().chain(actual.memory.keys()).collect::<BTreeSet<_>>();
+
+        for address in addresses {
+            let (left, right) = (expected.memory.get(address), actual.memory.get(address));
+
+            if left != right {
+                writeln!(f, "memory [{}] :: {} != {}", address, left.unwrap_or(&0), right.unwrap_or(&0))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::VecDeque;
+
+use crate::error::VmError;
+use crate::io::{Io, Queue};
+use crate::machine::{Machine, Status};
+use crate::observer::{Event, Observer, Store};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub ip: usize,
+    pub relative_base: i64,
+    pub status: Status,
+    pub writes: Vec<Store>,
+    pub input: Option<i64>,
+}
+
+#[derive(Clone, Debug)]
+struct Checkpoint {
+    step: u64,
+    machine: Machine,
+    inputs: Vec<i64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct History {
+    interval: usize,
+    limit: usize,
+    step: u64,
+    checkpoints: VecDeque<Checkpoint>,
+    entries: Vec<Entry>,
+}
+
+struct Recorder<'a> {
+    io: &'a mut dyn Io,
+    input: Option<i64>,
+}
+
+struct Writes(Vec<Store>);
+
+impl<'a> Io for Recorder<'a> {
+    fn read(&mut self) -> Option<i64> {
+        self.input = self.io.read();
+        self.input
+    }
+
+    fn write(&mut self, value: i64) {
+        self.io.write(value);
+    }
+}
+
+impl Observer for Writes {
+    fn observe(&mut self, event: &Event) {
+        self.0 = event.writes.clone();
+    }
+}
+
+fn execute(machine: &mut Machine, io: &mut dyn Io) -> Result<(Status, Option<Entry>), VmError> {
+    let halted = machine.is_halted();
+    let (ip, relative_base, status) = (machine.ip(), machine.relative_base(), machine.status().clone());
+    let mut recorder = Recorder { io, input: None };
+    let mut writes = Writes(vec![]);
+
+    let result = machine.step_observed(&mut recorder, &mut writes)?;
+
+    if halted || result == Status::NeedsInput {
+        return Ok((result, None));
+    }
+
+    let entry = Entry { ip, relative_base, status, writes: writes.0, input: recorder.input };
+
+    Ok((result, Some(entry)))
+}
+
+impl History {
+    pub fn new(interval: usize, limit: usize) -> History {
+        History {
+            interval: usize::max(interval, 1),
+            limit: usize::max(limit, 1),
+            step: 0,
+            checkpoints: VecDeque::new(),
+            entries: vec![],
+        }
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.step
+    }
+
+    pub fn earliest(&self) -> u64 {
+        self.checkpoints.front().map_or(self.step, |checkpoint| checkpoint.step)
+    }
+
+    pub fn clear(&mut self) {
+        self.step = 0;
+        self.checkpoints.clear();
+        self.entries.clear();
+    }
+
+    pub fn step(&mut self, machine: &mut Machine, queue: &mut Queue) -> Result<Status, VmError> {
+        if self.checkpoints.is_empty() || self.entries.len() == self.interval {
+            self.checkpoints.push_back(Checkpoint { step: self.step, machine: machine.clone(), inputs: vec![] });
+            self.entries.clear();
+
+            if self.checkpoints.len() > self.limit {
+                self.checkpoints.pop_front();
+            }
+        }
+
+        let (status, entry) = execute(machine, queue)?;
+
+        if let Some(entry) = entry {
+            if let (Some(input), Some(checkpoint)) = (entry.input, self.checkpoints.back_mut()) {
+                checkpoint.inputs.push(input);
+            }
+
+            self.entries.push(entry);
+            self.step += 1;
+        }
+
+        Ok(status)
+    }
+
+    fn replay(&mut self) -> bool {
+        if self.checkpoints.len() < 2 {
+            return false;
+        }
+
+        self.checkpoints.pop_back();
+
+        let checkpoint = self.checkpoints.back().unwrap();
+        let mut machine = checkpoint.machine.clone();
+        let mut queue = Queue::new(checkpoint.inputs.clone());
+
+        self.entries.clear();
+
+        while self.entries.len() < self.interval {
+            match execute(&mut machine, &mut queue) {
+                Ok((_, Some(entry))) => self.entries.push(entry),
+                _ => break,
+            }
+        }
+
+        true
+    }
+
+    pub fn undo(&mut self, machine: &mut Machine, queue: &mut Queue) -> Option<Entry> {
+        if self.entries.is_empty() && !self.replay() {
+            return None;
+        }
+
+        let entry = self.entries.pop()?;
+
+        for write in entry.writes.iter().rev() {
+            machine.write(write.address, write.old);
+        }
+
+        if let Some(input) = entry.input {
+            queue.unread(input);
+
+            if let Some(checkpoint) = self.checkpoints.back_mut() {
+                checkpoint.inputs.pop();
+            }
+        }
+
+        machine.set_ip(entry.ip);
+        machine.set_relative_base(entry.relative_base);
+        machine.set_status(entry.status.clone());
+        self.step -= 1;
+
+        Some(entry)
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cell::Cell;
+
+pub trait Io<C = i64> {
+    fn read(&mut self) -> Option<C>;
+    fn write(&mut self, value: C);
+}
+
+pub struct Terminal;
+
+impl<C: Cell> Io<C> for Terminal {
+    fn read(&mut self) -> Option<C> {
+        loop {
+            let mut input = String::new();
+
+            match io::stdin().read_line(&mut input) {
+                Ok(0) | Err(_) => return None,
+                Ok(_) => match input.trim().parse() {
+                    Ok(value) => return Some(value),
+                    Err(_) => println!("Error: invalid input '{}', expected an integer", input.trim()),
+                },
+            }
+        }
+    }
+
+    fn write(&mut self, value: C) {
+        println!("{}", value);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Queue<C = i64> {
+    input: VecDeque<C>,
+    output: Vec<C>,
+}
+
+impl<C> Default for Queue<C> {
+    fn default() -> Queue<C> {
+        Queue {
+            input: VecDeque::new(),
+            output: vec![],
+        }
+    }
+}
+
+impl<C> Queue<C> {
+    pub fn new(input: Vec<C>) -> Queue<C> {
+        Queue {
+            input: input.into_iter().collect(),
+            output: vec![],
+        }
+    }
+
+    pub fn push(&mut self, value: C) {
+        self.input.push_back(value);
+    }
+
+    pub fn unread(&mut self, value: C) {
+        self.input.push_front(value);
+    }
+
+    pub fn output(&self) -> &[C] {
+        &self.output
+    }
+
+    pub fn take_output(&mut self) -> Vec<C> {
+        std::mem::take(&mut self.output)
+    }
+
+    pub fn into_output(self) -> Vec<C> {
+        self.output
+    }
+}
+
+impl<C> Io<C> for Queue<C> {
+    fn read(&mut self) -> Option<C> {
+        self.input.pop_front()
+    }
+
+    fn write(&mut self, value: C) {
+        self.output.push(value);
+    }
+}
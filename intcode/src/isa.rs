@@ -0,0 +1,283 @@
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use crate::cell::Cell;
+use crate::error::ErrorKind;
+use crate::io::Io;
+use crate::machine::{Machine, Status};
+
+const OPCODE_LIMIT: usize = 100;
+const ARITY_LIMIT: usize = 17;
+
+pub type Handler<C> = Arc<dyn Fn(&mut Context<'_, C>) -> Result<(), ErrorKind> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct Instruction<C = i64> {
+    pub mnemonic: &'static str,
+    pub arity: usize,
+    pub writes: Vec<usize>,
+    pub handler: Handler<C>,
+}
+
+#[derive(Clone)]
+pub struct InstructionSet<C = i64> {
+    instructions: Vec<Option<Instruction<C>>>,
+}
+
+pub struct Context<'a, C = i64> {
+    machine: &'a mut Machine<C>,
+    io: &'a mut dyn Io<C>,
+    instruction: &'a Instruction<C>,
+    jumped: bool,
+}
+
+impl<'a, C: Cell> Context<'a, C> {
+    pub(crate) fn new(machine: &'a mut Machine<C>, io: &'a mut dyn Io<C>, instruction: &'a Instruction<C>) -> Context<'a, C> {
+        Context { machine, io, instruction, jumped: false }
+    }
+
+    pub(crate) fn jumped(&self) -> bool {
+        self.jumped
+    }
+
+    fn check_parameter(&self, n: usize) -> Result<(), ErrorKind> {
+        if n == 0 || n > self.instruction.arity {
+            return Err(ErrorKind::InvalidParameter(n));
+        }
+
+        Ok(())
+    }
+
+    pub fn ip(&self) -> usize {
+        self.machine.ip()
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.machine.relative_base()
+    }
+
+    pub fn peek(&self, address: usize) -> C {
+        self.machine.read(address)
+    }
+
+    pub fn read(&mut self, n: usize) -> Result<C, ErrorKind> {
+        self.check_parameter(n)?;
+        self.machine.get_operand(n)
+    }
+
+    pub fn dest(&self, n: usize) -> Result<usize, ErrorKind> {
+        if !self.instruction.writes.contains(&n) {
+            return Err(ErrorKind::InvalidParameter(n));
+        }
+
+        self.machine.get_dest(n)
+    }
+
+    pub fn store(&mut self, address: usize, value: C) {
+        self.machine.store(address, value);
+    }
+
+    pub fn write(&mut self, n: usize, value: C) -> Result<(), ErrorKind> {
+        let address = self.dest(n)?;
+
+        self.store(address, value);
+
+        Ok(())
+    }
+
+    pub fn input(&mut self) -> Option<C> {
+        self.io.read()
+    }
+
+    pub fn output(&mut self, value: C) {
+        self.io.write(value.clone());
+        self.machine.set_status(Status::Output(value));
+    }
+
+    pub fn jump(&mut self, target: &C) -> Result<(), ErrorKind> {
+        let target = target.to_i64().ok_or(ErrorKind::AddressOutOfRange)?;
+
+        if target < 0 {
+            return Err(ErrorKind::NegativeAddress(target));
+        }
+
+        self.machine.set_ip(target as usize);
+        self.jumped = true;
+
+        Ok(())
+    }
+
+    pub fn adjust_relative_base(&mut self, offset: &C) -> Result<(), ErrorKind> {
+        let offset = offset.to_i64().ok_or(ErrorKind::AddressOutOfRange)?;
+        let relative_base = self.machine.relative_base().checked_add(offset).ok_or(ErrorKind::Overflow)?;
+
+        self.machine.set_relative_base(relative_base);
+
+        Ok(())
+    }
+
+    pub fn wait(&mut self) {
+        self.machine.set_status(Status::NeedsInput);
+    }
+
+    pub fn halt(&mut self) {
+        self.machine.set_status(Status::Halted);
+    }
+}
+
+pub(crate) fn get_standard_set() -> &'static InstructionSet {
+    static STANDARD: OnceLock<InstructionSet> = OnceLock::new();
+
+    STANDARD.get_or_init(InstructionSet::standard)
+}
+
+fn arithmetic<C: Cell>(operation: fn(&C, &C) -> Option<C>) -> impl Fn(&mut Context<'_, C>) -> Result<(), ErrorKind> {
+    move |context| {
+        let left = context.read(1)?;
+        let right = context.read(2)?;
+        let dest = context.dest(3)?;
+
+        let result = operation(&left, &right).ok_or(ErrorKind::Overflow)?;
+
+        context.store(dest, result);
+
+        Ok(())
+    }
+}
+
+fn compare<C: Cell>(operation: fn(&C, &C) -> bool) -> impl Fn(&mut Context<'_, C>) -> Result<(), ErrorKind> {
+    move |context| {
+        let result = operation(&context.read(1)?, &context.read(2)?);
+        let dest = context.dest(3)?;
+
+        context.store(dest, C::from_i64(result as i64));
+
+        Ok(())
+    }
+}
+
+fn jump<C: Cell>(condition: bool) -> impl Fn(&mut Context<'_, C>) -> Result<(), ErrorKind> {
+    move |context| {
+        if context.read(1)?.is_zero() != condition {
+            let target = context.read(2)?;
+
+            context.jump(&target)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: Cell> InstructionSet<C> {
+    pub fn empty() -> InstructionSet<C> {
+        InstructionSet { instructions: vec![None; OPCODE_LIMIT] }
+    }
+
+    pub fn standard() -> InstructionSet<C> {
+        let mut set = InstructionSet::empty();
+
+        set.register(1, "ADD", 3, &[3], arithmetic(C::checked_add))
+            .register(2, "MUL", 3, &[3], arithmetic(C::checked_mul))
+            .register(3, "IN", 1, &[1], |context| {
+                let dest = context.dest(1)?;
+
+                match context.input() {
+                    Some(value) => context.store(dest, value),
+                    None => context.wait(),
+                }
+
+                Ok(())
+            })
+            .register(4, "OUT", 1, &[], |context| {
+                let value = context.read(1)?;
+
+                context.output(value);
+
+                Ok(())
+            })
+            .register(5, "JT", 2, &[], jump(true))
+            .register(6, "JF", 2, &[], jump(false))
+            .register(7, "LT", 3, &[3], compare(|left, right| left < right))
+            .register(8, "EQ", 3, &[3], compare(|left, right| left == right))
+            .register(9, "RBO", 1, &[], |context| {
+                let offset = context.read(1)?;
+
+                context.adjust_relative_base(&offset)
+            })
+            .register(99, "HALT", 0, &[], |context| {
+                context.halt();
+
+                Ok(())
+            });
+
+        set
+    }
+
+    pub fn register<F>(&mut self, opcode: i64, mnemonic: &'static str, arity: usize, writes: &[usize], handler: F) -> &mut InstructionSet<C>
+    where
+        F: Fn(&mut Context<'_, C>) -> Result<(), ErrorKind> + Send + Sync + 'static,
+    {
+        if opcode < 0 || opcode as usize >= OPCODE_LIMIT {
+            panic!("Opcode out of range :: {}", opcode);
+        }
+
+        if arity > ARITY_LIMIT {
+            panic!("Arity {} exceeds the limit of {} :: {}", arity, ARITY_LIMIT, mnemonic);
+        }
+
+        if let Some(n) = writes.iter().find(|n| **n == 0 || **n > arity) {
+            panic!("Write position {} outside arity {} :: {}", n, arity, mnemonic);
+        }
+
+        self.instructions[opcode as usize] = Some(Instruction {
+            mnemonic,
+            arity,
+            writes: writes.to_vec(),
+            handler: Arc::new(handler),
+        });
+
+        self
+    }
+
+    pub fn unregister(&mut self, opcode: i64) -> Option<Instruction<C>> {
+        self.instructions.get_mut(opcode as usize).and_then(|slot| slot.take())
+    }
+
+    pub fn get(&self, opcode: i64) -> Option<&Instruction<C>> {
+        if opcode < 0 {
+            return None;
+        }
+
+        self.instructions.get(opcode as usize).and_then(|slot| slot.as_ref())
+    }
+
+    pub fn opcodes(&self) -> impl Iterator<Item = i64> + '_ {
+        (0..OPCODE_LIMIT).filter(move |opcode| self.instructions[*opcode].is_some()).map(|opcode| opcode as i64)
+    }
+
+    pub fn find(&self, mnemonic: &str) -> Option<(i64, &Instruction<C>)> {
+        self.opcodes()
+            .map(|opcode| (opcode, self.get(opcode).unwrap()))
+            .find(|(_, instruction)| instruction.mnemonic.eq_ignore_ascii_case(mnemonic))
+    }
+}
+
+impl<C: Cell> Default for InstructionSet<C> {
+    fn default() -> InstructionSet<C> {
+        InstructionSet::standard()
+    }
+}
+
+impl<C> fmt::Debug for Instruction<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (arity {}, writes {:?})", self.mnemonic, self.arity, self.writes)
+    }
+}
+
+impl<C> fmt::Debug for InstructionSet<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let registered = self.instructions.iter().enumerate().filter_map(|(opcode, slot)| slot.as_ref().map(|instruction| (opcode, instruction)));
+
+        f.debug_map().entries(registered).finish()
+    }
+}
@@ -0,0 +1,679 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::asm::assemble;
+
+const KEYWORDS: [&str; 6] = ["fn", "let", "if", "else", "while", "return"];
+const SYMBOLS: [&str; 19] = ["==", "!=", "<=", ">=", "&&", "||", "(", ")", "{", "}", ",", ";", "=", "+", "-", "*", "<", ">", "!"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompileError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Number(i64),
+    Ident(String),
+    Keyword(&'static str),
+    Symbol(&'static str),
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Number(i64),
+    Var(String, usize),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>, usize),
+}
+
+#[derive(Clone, Debug)]
+enum Stmt {
+    Let(String, Expr),
+    Assign(String, Expr, usize),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    Return(Option<Expr>),
+    Expr(Expr),
+}
+
+#[derive(Clone, Debug)]
+struct Function {
+    name: String,
+    params: Vec<String>,
+    body: Vec<Stmt>,
+    line: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Arg {
+    Imm(i64),
+    Label(String),
+    Slot(i64),
+    Frame(i64),
+    FrameSize(i64),
+}
+
+enum Item {
+    Label(String),
+    Op(&'static str, Vec<Arg>),
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+struct Generator<'a> {
+    functions: &'a HashMap<String, usize>,
+    labels: &'a mut usize,
+    items: Vec<Item>,
+    scopes: Vec<HashMap<String, i64>>,
+    locals: i64,
+    next: i64,
+    frame: i64,
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for CompileError {}
+
+fn error<T>(line: usize, message: String) -> Result<T, CompileError> {
+    Err(CompileError { line, message })
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, CompileError> {
+    let mut tokens = vec![];
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut text = raw.split("//").next().unwrap_or("").trim_start();
+
+        while !text.is_empty() {
+            let first = text.chars().next().unwrap();
+            let end = match first {
+                '0'..='9' => text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len()),
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    text.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(text.len())
+                },
+                _ => match SYMBOLS.iter().find(|symbol| text.starts_with(*symbol)) {
+                    Some(symbol) => symbol.len(),
+                    None => return error(line, format!("unexpected character '{}'", first)),
+                },
+            };
+            let word = &text[..end];
+
+            let token = if first.is_ascii_digit() {
+                match word.parse() {
+                    Ok(value) => Token::Number(value),
+                    Err(_) => return error(line, format!("number too large '{}'", word)),
+                }
+            } else if let Some(keyword) = KEYWORDS.iter().find(|keyword| **keyword == word) {
+                Token::Keyword(keyword)
+            } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| **symbol == word) {
+                Token::Symbol(symbol)
+            } else {
+                Token::Ident(String::from(word))
+            };
+
+            tokens.push((token, line));
+            text = text[end..].trim_start();
+        }
+    }
+
+    Ok(tokens)
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        match self.tokens.get(self.position) {
+            Some((_, line)) => *line,
+            None => self.tokens.last().map_or(1, |(_, line)| *line),
+        }
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        match self.peek() {
+            Some(Token::Symbol(found)) | Some(Token::Keyword(found)) if *found == symbol => {
+                self.position += 1;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), CompileError> {
+        if self.eat(symbol) {
+            return Ok(());
+        }
+
+        match self.peek() {
+            Some(token) => error(self.line(), format!("expected '{}', found {}", symbol, describe(token))),
+            None => error(self.line(), format!("expected '{}' at end of input", symbol)),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, CompileError> {
+        match self.peek().cloned() {
+            Some(Token::Ident(name)) => {
+                self.position += 1;
+                Ok(name)
+            },
+            Some(token) => error(self.line(), format!("expected a name, found {}", describe(&token))),
+            None => error(self.line(), String::from("expected a name at end of input")),
+        }
+    }
+
+    fn program(&mut self) -> Result<Vec<Function>, CompileError> {
+        let mut functions = vec![];
+
+        while self.peek().is_some() {
+            let line = self.line();
+
+            self.expect("fn")?;
+
+            let name = self.ident()?;
+            let mut params = vec![];
+
+            self.expect("(")?;
+
+            if !self.eat(")") {
+                loop {
+                    let line = self.line();
+                    let param = self.ident()?;
+
+                    if params.contains(&param) {
+                        return error(line, format!("duplicate parameter '{}'", param));
+                    }
+
+                    params.push(param);
+
+                    if self.eat(")") {
+                        break;
+                    }
+
+                    self.expect(",")?;
+                }
+            }
+
+            let body = self.block()?;
+
+            functions.push(Function { name, params, body, line });
+        }
+
+        Ok(functions)
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>, CompileError> {
+        let mut statements = vec![];
+
+        self.expect("{")?;
+
+        while !self.eat("}") {
+            if self.peek().is_none() {
+                return error(self.line(), String::from("unclosed block"));
+            }
+
+            statements.push(self.statement()?);
+        }
+
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Stmt, CompileError> {
+        let line = self.line();
+
+        if self.eat("let") {
+            let name = self.ident()?;
+
+            self.expect("=")?;
+
+            let value = self.expression()?;
+
+            self.expect(";")?;
+
+            return Ok(Stmt::Let(name, value));
+        }
+
+        if self.eat("if") {
+            let condition = self.expression()?;
+            let then = self.block()?;
+            let otherwise = match (self.eat("else"), self.peek()) {
+                (true, Some(Token::Keyword("if"))) => vec![self.statement()?],
+                (true, _) => self.block()?,
+                (false, _) => vec![],
+            };
+
+            return Ok(Stmt::If(condition, then, otherwise));
+        }
+
+        if self.eat("while") {
+            let condition = self.expression()?;
+
+            return Ok(Stmt::While(condition, self.block()?));
+        }
+
+        if self.eat("return") {
+            let value = if self.eat(";") {
+                None
+            } else {
+                let value = self.expression()?;
+
+                self.expect(";")?;
+                Some(value)
+            };
+
+            return Ok(Stmt::Return(value));
+        }
+
+        if let (Some((Token::Ident(name), _)), Some((Token::Symbol("="), _))) = (self.tokens.get(self.position), self.tokens.get(self.position + 1)) {
+            let name = name.clone();
+
+            self.position += 2;
+
+            let value = self.expression()?;
+
+            self.expect(";")?;
+
+            return Ok(Stmt::Assign(name, value, line));
+        }
+
+        let value = self.expression()?;
+
+        self.expect(";")?;
+
+        Ok(Stmt::Expr(value))
+    }
+
+    fn expression(&mut self) -> Result<Expr, CompileError> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, CompileError> {
+        const LEVELS: [&[&str]; 5] = [&["||"], &["&&"], &["==", "!=", "<", ">", "<=", ">="], &["+", "-"], &["*"]];
+
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut left = self.binary(level + 1)?;
+
+        while let Some(Token::Symbol(symbol)) = self.peek().cloned() {
+            if !LEVELS[level].contains(&symbol) {
+                break;
+            }
+
+            self.position += 1;
+            left = Expr::Binary(symbol, Box::new(left), Box::new(self.binary(level + 1)?));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, CompileError> {
+        for symbol in ["-", "!"] {
+            if self.eat(symbol) {
+                return Ok(Expr::Unary(symbol, Box::new(self.unary()?)));
+            }
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, CompileError> {
+        let line = self.line();
+
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.position += 1;
+                Ok(Expr::Number(value))
+            },
+            Some(Token::Ident(name)) => {
+                self.position += 1;
+
+                if !self.eat("(") {
+                    return Ok(Expr::Var(name, line));
+                }
+
+                let mut args = vec![];
+
+                if !self.eat(")") {
+                    loop {
+                        args.push(self.expression()?);
+
+                        if self.eat(")") {
+                            break;
+                        }
+
+                        self.expect(",")?;
+                    }
+                }
+
+                Ok(Expr::Call(name, args, line))
+            },
+            Some(Token::Symbol("(")) => {
+                self.position += 1;
+
+                let value = self.expression()?;
+
+                self.expect(")")?;
+                Ok(value)
+            },
+            Some(token) => error(line, format!("expected an expression, found {}", describe(&token))),
+            None => error(line, String::from("expected an expression at end of input")),
+        }
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Number(value) => format!("number {}", value),
+        Token::Ident(name) => format!("name '{}'", name),
+        Token::Keyword(keyword) | Token::Symbol(keyword) => format!("'{}'", keyword),
+    }
+}
+
+impl<'a> Generator<'a> {
+    fn new(functions: &'a HashMap<String, usize>, labels: &'a mut usize, params: &[String]) -> Generator<'a> {
+        let scope = params.iter().enumerate().map(|(index, name)| (name.clone(), index as i64 + 1)).collect();
+        let locals = params.len() as i64 + 1;
+
+        Generator {
+            functions,
+            labels,
+            items: vec![],
+            scopes: vec![scope],
+            locals,
+            next: locals,
+            frame: locals,
+        }
+    }
+
+    fn label(&mut self, kind: &str) -> String {
+        *self.labels += 1;
+
+        format!("{}_{}", kind, self.labels)
+    }
+
+    fn emit(&mut self, mnemonic: &'static str, args: Vec<Arg>) {
+        self.items.push(Item::Op(mnemonic, args));
+    }
+
+    fn place(&mut self, label: &str) {
+        self.items.push(Item::Label(String::from(label)));
+    }
+
+    fn temp(&mut self) -> Arg {
+        let slot = self.next;
+
+        self.next += 1;
+        self.frame = i64::max(self.frame, self.next);
+
+        Arg::Slot(slot)
+    }
+
+    fn lookup(&self, name: &str, line: usize) -> Result<Arg, CompileError> {
+        match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+            Some(slot) => Ok(Arg::Slot(*slot)),
+            None => error(line, format!("undefined variable '{}'", name)),
+        }
+    }
+
+    fn block(&mut self, statements: &[Stmt]) -> Result<(), CompileError> {
+        self.scopes.push(HashMap::new());
+
+        for statement in statements {
+            self.statement(statement)?;
+            self.next = self.locals;
+        }
+
+        self.scopes.pop();
+
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &Stmt) -> Result<(), CompileError> {
+        match statement {
+            Stmt::Let(name, value) => {
+                let value = self.expression(value)?;
+                let slot = self.locals;
+
+                self.locals += 1;
+                self.next = self.locals;
+                self.frame = i64::max(self.frame, self.next);
+                self.scopes.last_mut().unwrap().insert(name.clone(), slot);
+                self.emit("ADD", vec![value, Arg::Imm(0), Arg::Slot(slot)]);
+            },
+            Stmt::Assign(name, value, line) => {
+                let slot = self.lookup(name, *line)?;
+                let value = self.expression(value)?;
+
+                self.emit("ADD", vec![value, Arg::Imm(0), slot]);
+            },
+            Stmt::If(condition, then, otherwise) => {
+                let (other, end) = (self.label("else"), self.label("endif"));
+                let condition = self.expression(condition)?;
+
+                self.emit("JF", vec![condition, Arg::Label(other.clone())]);
+                self.block(then)?;
+                self.emit("JT", vec![Arg::Imm(1), Arg::Label(end.clone())]);
+                self.place(&other);
+                self.block(otherwise)?;
+                self.place(&end);
+            },
+            Stmt::While(condition, body) => {
+                let (start, end) = (self.label("while"), self.label("endwhile"));
+
+                self.place(&start);
+
+                let condition = self.expression(condition)?;
+
+                self.emit("JF", vec![condition, Arg::Label(end.clone())]);
+                self.block(body)?;
+                self.emit("JT", vec![Arg::Imm(1), Arg::Label(start)]);
+                self.place(&end);
+            },
+            Stmt::Return(value) => {
+                let value = match value {
+                    Some(value) => self.expression(value)?,
+                    None => Arg::Imm(0),
+                };
+
+                self.emit("ADD", vec![value, Arg::Imm(0), Arg::Slot(1)]);
+                self.emit("JT", vec![Arg::Imm(1), Arg::Slot(0)]);
+            },
+            Stmt::Expr(value) => {
+                self.expression(value)?;
+            },
+        }
+
+        Ok(())
+    }
+
+    fn expression(&mut self, expr: &Expr) -> Result<Arg, CompileError> {
+        match expr {
+            Expr::Number(value) => Ok(Arg::Imm(*value)),
+            Expr::Var(name, line) => self.lookup(name, *line),
+            Expr::Unary(symbol, operand) => {
+                let operand = self.expression(operand)?;
+                let result = self.temp();
+
+                match *symbol {
+                    "-" => self.emit("MUL", vec![operand, Arg::Imm(-1), result.clone()]),
+                    _ => self.emit("EQ", vec![operand, Arg::Imm(0), result.clone()]),
+                }
+
+                Ok(result)
+            },
+            Expr::Binary(symbol, left, right) if *symbol == "&&" || *symbol == "||" => {
+                let end = self.label("logic");
+                let left = self.expression(left)?;
+                let result = self.temp();
+                let (jump, short) = if *symbol == "&&" { ("JF", 0) } else { ("JT", 1) };
+
+                self.emit("ADD", vec![Arg::Imm(short), Arg::Imm(0), result.clone()]);
+                self.emit(jump, vec![left, Arg::Label(end.clone())]);
+
+                let right = self.expression(right)?;
+
+                self.emit("EQ", vec![right, Arg::Imm(0), result.clone()]);
+                self.emit("EQ", vec![result.clone(), Arg::Imm(0), result.clone()]);
+                self.place(&end);
+
+                Ok(result)
+            },
+            Expr::Binary(symbol, left, right) => {
+                let left = self.expression(left)?;
+                let right = self.expression(right)?;
+                let result = self.temp();
+
+                match *symbol {
+                    "+" => self.emit("ADD", vec![left, right, result.clone()]),
+                    "*" => self.emit("MUL", vec![left, right, result.clone()]),
+                    "-" => {
+                        self.emit("MUL", vec![right, Arg::Imm(-1), result.clone()]);
+                        self.emit("ADD", vec![left, result.clone(), result.clone()]);
+                    },
+                    "<" => self.emit("LT", vec![left, right, result.clone()]),
+                    ">" => self.emit("LT", vec![right, left, result.clone()]),
+                    "==" => self.emit("EQ", vec![left, right, result.clone()]),
+                    _ => {
+                        match *symbol {
+                            "!=" => self.emit("EQ", vec![left, right, result.clone()]),
+                            "<=" => self.emit("LT", vec![right, left, result.clone()]),
+                            _ => self.emit("LT", vec![left, right, result.clone()]),
+                        }
+
+                        self.emit("EQ", vec![result.clone(), Arg::Imm(0), result.clone()]);
+                    },
+                }
+
+                Ok(result)
+            },
+            Expr::Call(name, args, line) => self.call(name, args, *line),
+        }
+    }
+
+    fn call(&mut self, name: &str, args: &[Expr], line: usize) -> Result<Arg, CompileError> {
+        let arity = match (name, self.functions.get(name)) {
+            ("input", _) => 0,
+            ("output", _) => 1,
+            (_, Some(arity)) => *arity,
+            (_, None) => return error(line, format!("undefined function '{}'", name)),
+        };
+
+        if args.len() != arity {
+            return error(line, format!("'{}' takes {} arguments, found {}", name, arity, args.len()));
+        }
+
+        let mut values = vec![];
+
+        for arg in args {
+            values.push(self.expression(arg)?);
+        }
+
+        let result = self.temp();
+
+        match name {
+            "input" => self.emit("IN", vec![result.clone()]),
+            "output" => {
+                self.emit("OUT", vec![values[0].clone()]);
+                self.emit("ADD", vec![Arg::Imm(0), Arg::Imm(0), result.clone()]);
+            },
+            _ => {
+                let back = self.label("return");
+
+                for (index, value) in values.into_iter().enumerate() {
+                    self.emit("ADD", vec![value, Arg::Imm(0), Arg::Frame(index as i64 + 1)]);
+                }
+
+                self.emit("ADD", vec![Arg::Label(back.clone()), Arg::Imm(0), Arg::Frame(0)]);
+                self.emit("RBO", vec![Arg::FrameSize(1)]);
+                self.emit("JT", vec![Arg::Imm(1), Arg::Label(format!("fn_{}", name))]);
+                self.place(&back);
+                self.emit("RBO", vec![Arg::FrameSize(-1)]);
+                self.emit("ADD", vec![Arg::Frame(1), Arg::Imm(0), result.clone()]);
+            },
+        }
+
+        Ok(result)
+    }
+
+    fn render(&self, name: &str, out: &mut String) {
+        let frame = i64::max(self.frame, 2);
+
+        out.push_str(&format!("fn_{}:\n", name));
+
+        for item in self.items.iter() {
+            match item {
+                Item::Label(label) => out.push_str(&format!("{}:\n", label)),
+                Item::Op(mnemonic, args) => {
+                    let args: Vec<String> = args.iter().map(|arg| match arg {
+                        Arg::Imm(value) => format!("#{}", value),
+                        Arg::Label(label) => format!("#{}", label),
+                        Arg::Slot(slot) => format!("rb+{}", slot),
+                        Arg::Frame(delta) => format!("rb+{}", frame + delta),
+                        Arg::FrameSize(sign) => format!("#{}", sign * frame),
+                    }).collect();
+
+                    out.push_str(format!("    {:<4} {}", mnemonic, args.join(", ")).trim_end());
+                    out.push('\n');
+                },
+            }
+        }
+    }
+}
+
+pub fn compile_to_asm(source: &str) -> Result<String, CompileError> {
+    let mut parser = Parser { tokens: tokenize(source)?, position: 0 };
+    let functions = parser.program()?;
+    let mut arities: HashMap<String, usize> = HashMap::new();
+
+    for function in functions.iter() {
+        if function.name == "input" || function.name == "output" {
+            return error(function.line, format!("'{}' is a built-in function", function.name));
+        }
+
+        if arities.insert(function.name.clone(), function.params.len()).is_some() {
+            return error(function.line, format!("duplicate function '{}'", function.name));
+        }
+    }
+
+    match arities.get("main") {
+        Some(0) => {},
+        Some(_) => return error(1, String::from("'main' must not take arguments")),
+        None => return error(1, String::from("missing 'main' function")),
+    }
+
+    let mut labels = 0;
+    let mut out = String::from("    RBO  #stack\n    ADD  #exit, #0, rb+0\n    JT   #1, #fn_main\nexit:\n    HALT\n");
+
+    for function in functions.iter() {
+        let mut generator = Generator::new(&arities, &mut labels, &function.params);
+
+        generator.block(&function.body)?;
+        generator.statement(&Stmt::Return(None))?;
+        generator.render(&function.name, &mut out);
+    }
+
+    out.push_str("stack:\n    DATA 0\n");
+
+    Ok(out)
+}
+
+pub fn compile(source: &str) -> Result<Vec<i64>, CompileError> {
+    let asm = compile_to_asm(source)?;
+
+    assemble(&asm).map_err(|e| CompileError { line: 0, message: format!("generated assembly is invalid: {}", e) })
+}
@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+
+use serde::Deserialize;
+
+mod amplifier;
+mod ascii;
+mod asm;
+mod canvas;
+mod cell;
+mod coverage;
+mod debugger;
+mod disasm;
+mod error;
+mod fast;
+mod flow;
+pub mod fuzz;
+mod history;
+mod io;
+mod isa;
+mod lang;
+mod machine;
+mod memory;
+mod network;
+mod observer;
+mod search;
+mod snapshot;
+mod symbolic;
+mod trace;
+
+pub use amplifier::{best_phases, permutations, run_chain, run_feedback, PhaseSetting};
+pub use ascii::{encode, Ascii};
+pub use asm::{assemble, assemble_with, to_csv, AsmError};
+pub use canvas::{Canvas, Robot};
+pub use cell::Cell;
+pub use coverage::Coverage;
+pub use debugger::Debugger;
+pub use disasm::{decode, decode_with, disassemble, disassemble_with, Line, Mode, Operand};
+pub use error::{ErrorKind, VmError};
+pub use fast::{Compiled, FastMachine};
+pub use flow::{analyze, analyze_with, Block, Edge, Flow};
+pub use history::{Entry, History};
+pub use io::{Io, Queue, Terminal};
+pub use isa::{Context, Handler, Instruction, InstructionSet};
+pub use lang::{compile, compile_to_asm, CompileError};
+pub use machine::{Machine, Status};
+pub use memory::Memory;
+pub use network::{Control, Finish, Monitor, Nat, Network, Packet};
+pub use observer::{Access, Event, Observer, Store};
+pub use search::{find_all_noun_verb, find_noun_verb, run_noun_verb};
+pub use snapshot::Snapshot;
+pub use symbolic::{evaluate_symbolic, Expr, Polynomial, SymbolicError};
+pub use trace::{Profile, Tracer};
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+
+#[derive(Deserialize, Debug)]
+struct Record {
+    opcodes: String,
+}
+
+pub fn parse_cells<C: Cell>(file_path: &str) -> Result<Vec<C>, Box<dyn Error>> {
+    let file = File::open(file_path)?;
+    let reader = BufReader::new(file);
+    let mut rdr = csv::ReaderBuilder::new().delimiter(b'\'').has_headers(false).from_reader(reader);
+
+    let mut vec: Vec<C> = vec![];
+
+    for result in rdr.deserialize() {
+        let record: Record = result?;
+
+        for thing in record.opcodes.trim().split(',') {
+            match thing.trim().parse() {
+                Ok(cell) => vec.push(cell),
+                Err(_) => return Err(format!("Invalid opcode :: {}", thing).into()),
+            }
+        }
+    }
+
+    Ok(vec)
+}
+
+pub fn parse_input(file_path: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+    parse_cells(file_path)
+}
+
+pub fn parse_values(values: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+    let mut vec = vec![];
+
+    for thing in values.split(',').filter(|thing| !thing.trim().is_empty()) {
+        match thing.trim().parse() {
+            Ok(value) => vec.push(value),
+            Err(_) => return Err(format!("Invalid input :: {}", thing).into()),
+        }
+    }
+
+    Ok(vec)
+}
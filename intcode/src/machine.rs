@@ -0,0 +1,303 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cell::Cell;
+use crate::disasm::{decode_with, Line};
+use crate::error::{ErrorKind, VmError};
+use crate::io::{Io, Queue};
+use crate::isa::{Context, InstructionSet};
+use crate::memory::Memory;
+use crate::observer::{Access, Event, Observer, Store};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status<C = i64> {
+    Running,
+    NeedsInput,
+    Output(C),
+    Halted,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "C: Cell + Deserialize<'de>"))]
+pub struct Machine<C = i64> {
+    memory: Memory<C>,
+    ip: usize,
+    relative_base: i64,
+    status: Status<C>,
+    #[serde(skip, default = "no_journal")]
+    journal: Option<Event<C>>,
+    #[serde(skip, default = "standard_set")]
+    instructions: Arc<InstructionSet<C>>,
+}
+
+fn no_journal<C>() -> Option<Event<C>> {
+    None
+}
+
+fn standard_set<C: Cell>() -> Arc<InstructionSet<C>> {
+    Arc::new(InstructionSet::standard())
+}
+
+pub(crate) fn get_nth_parameter_mode(instruction: i64, n: usize) -> i64 {
+    let magnitude = match n {
+        1 => 100,
+        2 => 1000,
+        3 => 10000,
+        _ => 10_i64.pow(n as u32 + 1),
+    };
+
+    (instruction / magnitude) % 10
+}
+
+fn to_address(address: i64) -> Result<usize, ErrorKind> {
+    if address < 0 {
+        return Err(ErrorKind::NegativeAddress(address));
+    }
+
+    Ok(address as usize)
+}
+
+fn to_i64<C: Cell>(value: &C) -> Result<i64, ErrorKind> {
+    value.to_i64().ok_or(ErrorKind::AddressOutOfRange)
+}
+
+impl<C: Cell> Machine<C> {
+    pub fn new(program: Vec<C>) -> Machine<C> {
+        Machine::with_instruction_set(program, standard_set())
+    }
+
+    pub fn with_instruction_set(program: Vec<C>, instructions: Arc<InstructionSet<C>>) -> Machine<C> {
+        Machine {
+            memory: Memory::new(program),
+            ip: 0,
+            relative_base: 0,
+            status: Status::Running,
+            journal: None,
+            instructions,
+        }
+    }
+
+    pub(crate) fn from_parts(memory: Memory<C>, ip: usize, relative_base: i64, status: Status<C>) -> Machine<C> {
+        Machine {
+            memory,
+            ip,
+            relative_base,
+            status,
+            journal: None,
+            instructions: standard_set(),
+        }
+    }
+
+    pub fn memory(&self) -> &Memory<C> {
+        &self.memory
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn status(&self) -> &Status<C> {
+        &self.status
+    }
+
+    pub fn instruction_set(&self) -> &Arc<InstructionSet<C>> {
+        &self.instructions
+    }
+
+    pub fn set_instruction_set(&mut self, instructions: Arc<InstructionSet<C>>) {
+        self.instructions = instructions;
+    }
+
+    pub(crate) fn set_ip(&mut self, ip: usize) {
+        self.ip = ip;
+    }
+
+    pub(crate) fn set_relative_base(&mut self, relative_base: i64) {
+        self.relative_base = relative_base;
+    }
+
+    pub(crate) fn set_status(&mut self, status: Status<C>) {
+        self.status = status;
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.status == Status::Halted
+    }
+
+    pub fn read(&self, address: usize) -> C {
+        self.memory.get(address)
+    }
+
+    pub fn write(&mut self, address: usize, value: C) {
+        self.memory.set(address, value);
+    }
+
+    fn instruction(&self) -> Result<i64, ErrorKind> {
+        if self.ip >= self.memory.len() {
+            return Err(ErrorKind::IpOutOfBounds);
+        }
+
+        self.memory.get(self.ip).to_i64().ok_or(ErrorKind::UnknownOpcode)
+    }
+
+    pub(crate) fn get_dest(&self, n: usize) -> Result<usize, ErrorKind> {
+        let target = self.ip + n;
+        let parameter = self.memory.get(target);
+
+        match get_nth_parameter_mode(self.instruction()?, n) {
+            0 => to_address(to_i64(&parameter)?),
+            1 => Ok(target),
+            2 => match self.relative_base.checked_add(to_i64(&parameter)?) {
+                Some(address) => to_address(address),
+                None => Err(ErrorKind::AddressOutOfRange),
+            },
+            mode => Err(ErrorKind::UnknownMode(mode)),
+        }
+    }
+
+    pub(crate) fn get_operand(&mut self, n: usize) -> Result<C, ErrorKind> {
+        let address = self.get_dest(n)?;
+        let value = self.memory.get(address);
+
+        if let Some(event) = &mut self.journal {
+            event.reads.push(Access { address, value: value.clone() });
+        }
+
+        Ok(value)
+    }
+
+    pub(crate) fn store(&mut self, address: usize, value: C) {
+        if let Some(event) = &mut self.journal {
+            let old = self.memory.get(address);
+
+            event.writes.push(Store { address, old, value: value.clone() });
+        }
+
+        self.memory.set(address, value);
+    }
+
+    fn execute(&mut self, io: &mut dyn Io<C>) -> Result<(), ErrorKind> {
+        let opcode = self.instruction()? % 100;
+        let instructions = self.instructions.clone();
+        let instruction = instructions.get(opcode).ok_or(ErrorKind::UnknownOpcode)?;
+
+        let mut context = Context::new(self, io, instruction);
+
+        (instruction.handler)(&mut context)?;
+
+        if !context.jumped() {
+            match self.status {
+                Status::Running | Status::Output(_) => self.ip += instruction.arity + 1,
+                Status::NeedsInput | Status::Halted => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn step(&mut self, io: &mut dyn Io<C>) -> Result<Status<C>, VmError<C>> {
+        if self.status == Status::Halted {
+            return Ok(Status::Halted);
+        }
+
+        self.status = Status::Running;
+
+        match self.execute(io) {
+            Ok(()) => Ok(self.status.clone()),
+            Err(kind) => Err(VmError {
+                ip: self.ip,
+                instruction: self.memory.get(self.ip),
+                kind,
+            }),
+        }
+    }
+
+    fn begin_event(&self) -> Event<C> {
+        let instruction = self.memory.get(self.ip);
+        let opcode = instruction.to_i64().unwrap_or(0) % 100;
+        let memory = &self.memory;
+        let fetch = |address| if address < memory.len() { memory.get(address).to_i64() } else { None };
+
+        let (mnemonic, operands) = match decode_with(&self.instructions, &fetch, self.ip) {
+            Some(Line::Instruction { mnemonic, operands, .. }) => (mnemonic, operands),
+            _ => (self.instructions.get(opcode).map_or("DATA", |instruction| instruction.mnemonic), vec![]),
+        };
+
+        Event {
+            ip: self.ip,
+            instruction,
+            opcode,
+            mnemonic,
+            operands,
+            reads: vec![],
+            writes: vec![],
+            relative_base: self.relative_base,
+            next_ip: self.ip,
+        }
+    }
+
+    pub fn step_observed(&mut self, io: &mut dyn Io<C>, observer: &mut dyn Observer<C>) -> Result<Status<C>, VmError<C>> {
+        if self.status == Status::Halted {
+            return Ok(Status::Halted);
+        }
+
+        self.journal = Some(self.begin_event());
+
+        let result = self.step(io);
+        let mut event = self.journal.take().unwrap();
+
+        if let Ok(status) = &result {
+            if *status != Status::NeedsInput {
+                event.relative_base = self.relative_base;
+                event.next_ip = self.ip;
+                observer.observe(&event);
+            }
+        }
+
+        result
+    }
+
+    fn drive(&mut self, io: &mut dyn Io<C>, mut observer: Option<&mut dyn Observer<C>>, pause_on_output: bool) -> Result<Status<C>, VmError<C>> {
+        loop {
+            let status = match &mut observer {
+                Some(observer) => self.step_observed(io, *observer)?,
+                None => self.step(io)?,
+            };
+
+            match status {
+                Status::Running => continue,
+                Status::Output(_) if !pause_on_output => continue,
+                status => return Ok(status),
+            }
+        }
+    }
+
+    pub fn resume(&mut self, io: &mut dyn Io<C>) -> Result<Status<C>, VmError<C>> {
+        self.drive(io, None, true)
+    }
+
+    pub fn resume_observed(&mut self, io: &mut dyn Io<C>, observer: &mut dyn Observer<C>) -> Result<Status<C>, VmError<C>> {
+        self.drive(io, Some(observer), true)
+    }
+
+    pub fn run(&mut self, io: &mut dyn Io<C>) -> Result<Status<C>, VmError<C>> {
+        self.drive(io, None, false)
+    }
+
+    pub fn run_observed(&mut self, io: &mut dyn Io<C>, observer: &mut dyn Observer<C>) -> Result<Status<C>, VmError<C>> {
+        self.drive(io, Some(observer), false)
+    }
+
+    pub fn run_with_input(&mut self, input: Vec<C>) -> Result<Vec<C>, VmError<C>> {
+        let mut queue = Queue::new(input);
+
+        self.run(&mut queue)?;
+
+        Ok(queue.into_output())
+    }
+}
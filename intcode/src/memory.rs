@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cell::Cell;
+
+const DENSE_LIMIT: usize = 1 << 20;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Memory<C = i64> {
+    dense: Vec<C>,
+    sparse: HashMap<usize, C>,
+    len: usize,
+}
+
+impl<C: Cell> Memory<C> {
+    pub fn new(image: Vec<C>) -> Memory<C> {
+        Memory {
+            len: image.len(),
+            dense: image,
+            sparse: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn as_slice(&self) -> &[C] {
+        &self.dense
+    }
+
+    pub fn get(&self, address: usize) -> C {
+        match self.dense.get(address) {
+            Some(value) => value.clone(),
+            None => self.sparse.get(&address).cloned().unwrap_or_default(),
+        }
+    }
+
+    pub fn set(&mut self, address: usize, value: C) {
+        if address < self.dense.len() {
+            self.dense[address] = value;
+        } else if address < DENSE_LIMIT {
+            self.dense.resize(address + 1, C::default());
+            self.dense[address] = value;
+        } else {
+            self.sparse.insert(address, value);
+        }
+
+        self.len = usize::max(self.len, address + 1);
+    }
+
+    pub fn range(&self, start: usize, end: usize) -> Vec<C> {
+        (start..end).map(|address| self.get(address)).collect()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (usize, &C)> {
+        self.dense.iter().enumerate().chain(self.sparse.iter().map(|(address, value)| (*address, value)))
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+
+use crate::error::VmError;
+use crate::io::Io;
+use crate::machine::{Machine, Status};
+
+const SLICE: usize = 1000;
+const NAT_ADDRESS: i64 = 255;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub address: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Send(Packet),
+    Stop,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Finish {
+    Halted,
+    Stopped,
+    Deadlocked,
+}
+
+pub trait Monitor {
+    fn on_packet(&mut self, _from: usize, _packet: &Packet) -> Control {
+        Control::Continue
+    }
+
+    fn on_idle(&mut self) -> Control {
+        Control::Continue
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Nic {
+    input: VecDeque<i64>,
+    output: Vec<i64>,
+    idle: bool,
+}
+
+impl Io for Nic {
+    fn read(&mut self) -> Option<i64> {
+        match self.input.pop_front() {
+            Some(value) => Some(value),
+            None => {
+                self.idle = true;
+                Some(-1)
+            },
+        }
+    }
+
+    fn write(&mut self, value: i64) {
+        self.output.push(value);
+        self.idle = false;
+    }
+}
+
+pub struct Network {
+    machines: Vec<Machine>,
+    nics: Vec<Nic>,
+}
+
+impl Network {
+    pub fn new(program: &[i64], size: usize) -> Network {
+        let machines = (0..size).map(|_| Machine::new(program.to_vec())).collect();
+        let nics = (0..size)
+            .map(|address| Nic {
+                input: vec![address as i64].into_iter().collect(),
+                ..Nic::default()
+            })
+            .collect();
+
+        Network { machines, nics }
+    }
+
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    pub fn machine(&self, address: usize) -> &Machine {
+        &self.machines[address]
+    }
+
+    pub fn is_idle(&self) -> bool {
+        let running = self.machines.iter().zip(self.nics.iter()).filter(|(machine, _)| !machine.is_halted());
+
+        running.map(|(_, nic)| nic).all(|nic| nic.idle && nic.input.is_empty())
+    }
+
+    pub fn send(&mut self, packet: Packet) -> bool {
+        if packet.address < 0 || packet.address as usize >= self.nics.len() {
+            return false;
+        }
+
+        let nic = &mut self.nics[packet.address as usize];
+
+        nic.input.push_back(packet.x);
+        nic.input.push_back(packet.y);
+        nic.idle = false;
+
+        true
+    }
+
+    fn route(&mut self, from: usize, packet: Packet, monitor: &mut dyn Monitor) -> bool {
+        match monitor.on_packet(from, &packet) {
+            Control::Stop => return false,
+            Control::Send(extra) => {
+                self.send(extra);
+            },
+            Control::Continue => {},
+        }
+
+        self.send(packet);
+
+        true
+    }
+
+    fn run_slice(&mut self, address: usize, monitor: &mut dyn Monitor) -> Result<bool, VmError> {
+        self.nics[address].idle = false;
+
+        for _ in 0..SLICE {
+            let status = self.machines[address].step(&mut self.nics[address])?;
+
+            if self.nics[address].output.len() >= 3 {
+                let output: Vec<i64> = self.nics[address].output.drain(..3).collect();
+                let packet = Packet { address: output[0], x: output[1], y: output[2] };
+
+                if !self.route(address, packet, monitor) {
+                    return Ok(false);
+                }
+            }
+
+            if status == Status::Halted || self.nics[address].idle {
+                break;
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub fn step_round(&mut self, monitor: &mut dyn Monitor) -> Result<Control, VmError> {
+        for address in 0..self.machines.len() {
+            if self.machines[address].is_halted() {
+                continue;
+            }
+
+            if !self.run_slice(address, monitor)? {
+                return Ok(Control::Stop);
+            }
+        }
+
+        if !self.is_idle() {
+            return Ok(Control::Continue);
+        }
+
+        let control = monitor.on_idle();
+
+        if let Control::Send(packet) = control {
+            self.send(packet);
+        }
+
+        Ok(control)
+    }
+
+    fn is_running(&self) -> bool {
+        self.machines.iter().any(|machine| !machine.is_halted())
+    }
+
+    pub fn run(&mut self, monitor: &mut dyn Monitor) -> Result<Finish, VmError> {
+        while self.is_running() {
+            if self.step_round(monitor)? == Control::Stop {
+                return Ok(Finish::Stopped);
+            }
+
+            if self.is_running() && self.is_idle() {
+                return Ok(Finish::Deadlocked);
+            }
+        }
+
+        Ok(Finish::Halted)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Nat {
+    pub first: Option<Packet>,
+    pub last: Option<Packet>,
+    pub last_sent: Option<Packet>,
+    pub repeated_y: Option<i64>,
+}
+
+impl Nat {
+    pub fn new() -> Nat {
+        Nat::default()
+    }
+}
+
+impl Monitor for Nat {
+    fn on_packet(&mut self, _from: usize, packet: &Packet) -> Control {
+        if packet.address == NAT_ADDRESS {
+            self.first.get_or_insert(*packet);
+            self.last = Some(*packet);
+        }
+
+        Control::Continue
+    }
+
+    fn on_idle(&mut self) -> Control {
+        let packet = match self.last {
+            Some(packet) => Packet { address: 0, ..packet },
+            None => return Control::Continue,
+        };
+
+        if self.last_sent.map(|sent| sent.y) == Some(packet.y) {
+            self.repeated_y = Some(packet.y);
+            return Control::Stop;
+        }
+
+        self.last_sent = Some(packet);
+
+        Control::Send(packet)
+    }
+}
@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+use crate::disasm::Operand;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Access<C = i64> {
+    pub address: usize,
+    pub value: C,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Store<C = i64> {
+    pub address: usize,
+    pub old: C,
+    pub value: C,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Event<C = i64> {
+    pub ip: usize,
+    pub instruction: C,
+    pub opcode: i64,
+    pub mnemonic: &'static str,
+    pub operands: Vec<Operand>,
+    pub reads: Vec<Access<C>>,
+    pub writes: Vec<Store<C>>,
+    pub relative_base: i64,
+    pub next_ip: usize,
+}
+
+pub trait Observer<C = i64> {
+    fn observe(&mut self, event: &Event<C>);
+}
+
+impl<C, A: Observer<C>, B: Observer<C>> Observer<C> for (A, B) {
+    fn observe(&mut self, event: &Event<C>) {
+        self.0.observe(event);
+        self.1.observe(event);
+    }
+}
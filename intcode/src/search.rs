@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::error::VmError;
+use crate::fast::Compiled;
+
+const NOUN_VERB_LIMIT: usize = 100;
+
+pub fn run_noun_verb(program: &Compiled, noun: i64, verb: i64) -> Result<i64, VmError> {
+    let mut machine = program.machine();
+
+    machine.write(1, noun);
+    machine.write(2, verb);
+
+    machine.run_with_input(vec![])?;
+
+    Ok(machine.read(0))
+}
+
+fn get_pair(index: usize) -> (i64, i64) {
+    ((index / NOUN_VERB_LIMIT) as i64, (index % NOUN_VERB_LIMIT) as i64)
+}
+
+fn get_threads() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+pub fn find_all_noun_verb(image: &[i64], target: i64) -> Vec<(i64, i64)> {
+    let threads = get_threads();
+    let program = Compiled::new(image);
+    let matches = Mutex::new(vec![]);
+
+    thread::scope(|scope| {
+        for offset in 0..threads {
+            let matches = &matches;
+            let program = &program;
+
+            scope.spawn(move || {
+                for index in (offset..NOUN_VERB_LIMIT * NOUN_VERB_LIMIT).step_by(threads) {
+                    let (noun, verb) = get_pair(index);
+
+                    if run_noun_verb(program, noun, verb).ok() == Some(target) {
+                        matches.lock().unwrap().push((noun, verb));
+                    }
+                }
+            });
+        }
+    });
+
+    let mut matches = matches.into_inner().unwrap();
+
+    matches.sort();
+
+    matches
+}
+
+pub fn find_noun_verb(image: &[i64], target: i64) -> Option<(i64, i64)> {
+    let threads = get_threads();
+    let program = Compiled::new(image);
+    let first = AtomicUsize::new(usize::MAX);
+
+    thread::scope(|scope| {
+        for offset in 0..threads {
+            let first = &first;
+            let program = &program;
+
+            scope.spawn(move || {
+                for index in (offset..NOUN_VERB_LIMIT * NOUN_VERB_LIMIT).step_by(threads) {
+                    if index > first.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    let (noun, verb) = get_pair(index);
+
+                    if run_noun_verb(program, noun, verb).ok() == Some(target) {
+                        first.fetch_min(index, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    match first.into_inner() {
+        usize::MAX => None,
+        index => Some(get_pair(index)),
+    }
+}
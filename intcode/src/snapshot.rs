@@ -0,0 +1,43 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::cell::Cell;
+use crate::io::Queue;
+use crate::isa::InstructionSet;
+use crate::machine::Machine;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "C: Cell + Deserialize<'de>"))]
+pub struct Snapshot<C = i64> {
+    pub machine: Machine<C>,
+    pub queue: Queue<C>,
+}
+
+impl<C: Cell + Serialize + DeserializeOwned> Snapshot<C> {
+    pub fn new(machine: Machine<C>, queue: Queue<C>) -> Snapshot<C> {
+        Snapshot { machine, queue }
+    }
+
+    pub fn save(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let mut writer = BufWriter::new(File::create(file_path)?);
+
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    pub fn load(file_path: &str, instructions: Arc<InstructionSet<C>>) -> Result<Snapshot<C>, Box<dyn Error>> {
+        let reader = BufReader::new(File::open(file_path)?);
+        let mut snapshot: Snapshot<C> = serde_json::from_reader(reader)?;
+
+        snapshot.machine.set_instruction_set(instructions);
+
+        Ok(snapshot)
+    }
+}
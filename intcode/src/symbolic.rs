@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+const NOUN_VERB_LIMIT: i64 = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Const(i64),
+    Noun,
+    Verb,
+    Add(Rc<Expr>, Rc<Expr>),
+    Mul(Rc<Expr>, Rc<Expr>),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polynomial {
+    terms: BTreeMap<(u32, u32), i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolicError {
+    pub ip: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SymbolicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at ip {}", self.message, self.ip)
+    }
+}
+
+impl Error for SymbolicError {}
+
+fn add(left: &Rc<Expr>, right: &Rc<Expr>) -> Option<Rc<Expr>> {
+    let expr = match (left.as_ref(), right.as_ref()) {
+        (Expr::Const(a), Expr::Const(b)) => Rc::new(Expr::Const(a.checked_add(*b)?)),
+        (Expr::Const(0), _) => right.clone(),
+        (_, Expr::Const(0)) => left.clone(),
+        _ => Rc::new(Expr::Add(left.clone(), right.clone())),
+    };
+
+    Some(expr)
+}
+
+fn mul(left: &Rc<Expr>, right: &Rc<Expr>) -> Option<Rc<Expr>> {
+    let expr = match (left.as_ref(), right.as_ref()) {
+        (Expr::Const(a), Expr::Const(b)) => Rc::new(Expr::Const(a.checked_mul(*b)?)),
+        (Expr::Const(0), _) | (_, Expr::Const(0)) => Rc::new(Expr::Const(0)),
+        (Expr::Const(1), _) => right.clone(),
+        (_, Expr::Const(1)) => left.clone(),
+        _ => Rc::new(Expr::Mul(left.clone(), right.clone())),
+    };
+
+    Some(expr)
+}
+
+pub fn evaluate_symbolic(image: &[i64]) -> Result<Rc<Expr>, SymbolicError> {
+    let mut memory: Vec<Option<Rc<Expr>>> = image.iter().map(|word| Some(Rc::new(Expr::Const(*word)))).collect();
+    let mut ip = 0;
+
+    if memory.len() < 3 {
+        return Err(SymbolicError { ip, message: String::from("program too short for a noun and verb") });
+    }
+
+    memory[1] = Some(Rc::new(Expr::Noun));
+    memory[2] = Some(Rc::new(Expr::Verb));
+
+    loop {
+        let concrete = |address: usize| -> Result<Option<usize>, SymbolicError> {
+            match memory.get(address).map(|expr| expr.as_deref()) {
+                Some(Some(Expr::Const(value))) if *value >= 0 && (*value as usize) < memory.len() => Ok(Some(*value as usize)),
+                Some(Some(Expr::Const(value))) => Err(SymbolicError { ip, message: format!("address {} out of bounds", value) }),
+                Some(_) => Ok(None),
+                None => Err(SymbolicError { ip, message: format!("address {} out of bounds", address) }),
+            }
+        };
+
+        let opcode = match memory.get(ip).map(|expr| expr.as_deref()) {
+            Some(Some(Expr::Const(opcode))) => *opcode,
+            None => return Err(SymbolicError { ip, message: String::from("ran past the end of the program") }),
+            _ => return Err(SymbolicError { ip, message: String::from("opcode depends on noun or verb") }),
+        };
+
+        if opcode == 99 {
+            break;
+        }
+
+        if ip + 3 >= memory.len() {
+            return Err(SymbolicError { ip, message: String::from("instruction runs past the end of the program") });
+        }
+
+        let left = concrete(ip + 1)?.and_then(|address| memory[address].clone());
+        let right = concrete(ip + 2)?.and_then(|address| memory[address].clone());
+        let dest = match concrete(ip + 3)? {
+            Some(dest) => dest,
+            None => return Err(SymbolicError { ip, message: String::from("write address depends on noun or verb") }),
+        };
+
+        let overflow = || SymbolicError { ip, message: String::from("arithmetic overflow") };
+
+        memory[dest] = match (opcode, left, right) {
+            (1, Some(left), Some(right)) => Some(add(&left, &right).ok_or_else(overflow)?),
+            (2, Some(left), Some(right)) => Some(mul(&left, &right).ok_or_else(overflow)?),
+            (1, _, _) | (2, _, _) => None,
+            _ => return Err(SymbolicError { ip, message: format!("unsupported opcode {}", opcode) }),
+        };
+
+        ip += 4;
+    }
+
+    match memory[0].clone() {
+        Some(expr) => Ok(expr),
+        None => Err(SymbolicError { ip, message: String::from("result reads a cell addressed by noun or verb") }),
+    }
+}
+
+impl Expr {
+    pub fn evaluate(&self, noun: i64, verb: i64) -> Option<i64> {
+        match self {
+            Expr::Const(value) => Some(*value),
+            Expr::Noun => Some(noun),
+            Expr::Verb => Some(verb),
+            Expr::Add(left, right) => left.evaluate(noun, verb)?.checked_add(right.evaluate(noun, verb)?),
+            Expr::Mul(left, right) => left.evaluate(noun, verb)?.checked_mul(right.evaluate(noun, verb)?),
+        }
+    }
+
+    pub fn to_polynomial(&self) -> Option<Polynomial> {
+        match self {
+            Expr::Const(value) => Some(Polynomial::monomial((0, 0), *value)),
+            Expr::Noun => Some(Polynomial::monomial((1, 0), 1)),
+            Expr::Verb => Some(Polynomial::monomial((0, 1), 1)),
+            Expr::Add(left, right) => left.to_polynomial()?.add(&right.to_polynomial()?),
+            Expr::Mul(left, right) => left.to_polynomial()?.mul(&right.to_polynomial()?),
+        }
+    }
+}
+
+impl Polynomial {
+    fn monomial(powers: (u32, u32), coefficient: i64) -> Polynomial {
+        let mut polynomial = Polynomial::default();
+
+        if coefficient != 0 {
+            polynomial.terms.insert(powers, coefficient);
+        }
+
+        polynomial
+    }
+
+    fn insert(&mut self, powers: (u32, u32), coefficient: i64) -> Option<()> {
+        let total = self.terms.get(&powers).cloned().unwrap_or(0).checked_add(coefficient)?;
+
+        if total == 0 {
+            self.terms.remove(&powers);
+        } else {
+            self.terms.insert(powers, total);
+        }
+
+        Some(())
+    }
+
+    pub fn add(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut result = self.clone();
+
+        for (powers, coefficient) in other.terms.iter() {
+            result.insert(*powers, *coefficient)?;
+        }
+
+        Some(result)
+    }
+
+    pub fn mul(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut result = Polynomial::default();
+
+        for ((a, b), left) in self.terms.iter() {
+            for ((c, d), right) in other.terms.iter() {
+                result.insert((a.checked_add(*c)?, b.checked_add(*d)?), left.checked_mul(*right)?)?;
+            }
+        }
+
+        Some(result)
+    }
+
+    pub fn evaluate(&self, noun: i64, verb: i64) -> Option<i64> {
+        self.terms.iter().try_fold(0_i64, |total, ((a, b), coefficient)| {
+            let term = coefficient.checked_mul(noun.checked_pow(*a)?)?.checked_mul(verb.checked_pow(*b)?)?;
+
+            total.checked_add(term)
+        })
+    }
+
+    fn verb_coefficients(&self, noun: i64) -> Option<BTreeMap<u32, i64>> {
+        let mut coefficients = BTreeMap::new();
+
+        for ((a, b), coefficient) in self.terms.iter() {
+            let entry = coefficients.entry(*b).or_insert(0_i64);
+
+            *entry = entry.checked_add(coefficient.checked_mul(noun.checked_pow(*a)?)?)?;
+        }
+
+        Some(coefficients)
+    }
+
+    fn solve_linear(&self, noun: i64, target: i64) -> Option<Vec<i64>> {
+        let coefficients = self.verb_coefficients(noun)?;
+        let constant = coefficients.get(&0).cloned().unwrap_or(0);
+        let linear = coefficients.get(&1).cloned().unwrap_or(0);
+
+        if coefficients.keys().any(|power| *power > 1) {
+            return None;
+        }
+
+        if linear == 0 {
+            return Some(if constant == target { (0..NOUN_VERB_LIMIT).collect() } else { vec![] });
+        }
+
+        let difference = target.checked_sub(constant)?;
+
+        if difference % linear != 0 {
+            return Some(vec![]);
+        }
+
+        Some(vec![difference / linear].into_iter().filter(|verb| (0..NOUN_VERB_LIMIT).contains(verb)).collect())
+    }
+
+    pub fn solve(&self, target: i64) -> Vec<(i64, i64)> {
+        let mut solutions = vec![];
+
+        for noun in 0..NOUN_VERB_LIMIT {
+            let verbs = match self.solve_linear(noun, target) {
+                Some(verbs) => verbs,
+                None => (0..NOUN_VERB_LIMIT).filter(|verb| self.evaluate(noun, *verb) == Some(target)).collect(),
+            };
+
+            solutions.extend(verbs.into_iter().map(|verb| (noun, verb)));
+        }
+
+        solutions
+    }
+}
+
+fn format_monomial(noun: u32, verb: u32) -> String {
+    let format_power = |name: &str, power: u32| match power {
+        0 => None,
+        1 => Some(String::from(name)),
+        _ => Some(format!("{}^{}", name, power)),
+    };
+
+    let factors: Vec<String> = vec![format_power("noun", noun), format_power("verb", verb)].into_iter().flatten().collect();
+
+    factors.join("*")
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        for (index, ((noun, verb), coefficient)) in self.terms.iter().rev().enumerate() {
+            let monomial = format_monomial(*noun, *verb);
+            let magnitude = coefficient.abs();
+
+            match (index, *coefficient < 0) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {},
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+
+            match (monomial.is_empty(), magnitude) {
+                (true, _) => write!(f, "{}", magnitude)?,
+                (false, 1) => write!(f, "{}", monomial)?,
+                (false, _) => write!(f, "{}*{}", magnitude, monomial)?,
+            }
+        }
+
+        Ok(())
+    }
+}
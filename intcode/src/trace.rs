@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::observer::{Event, Observer};
+
+pub struct Tracer<W: Write> {
+    out: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Tracer<W> {
+    pub fn new(out: W) -> Tracer<W> {
+        Tracer { out, error: None }
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        self.out.flush()?;
+
+        Ok(self.out)
+    }
+}
+
+impl<C: Serialize, W: Write> Observer<C> for Tracer<W> {
+    fn observe(&mut self, event: &Event<C>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = serde_json::to_writer(&mut self.out, event)
+            .map_err(io::Error::from)
+            .and_then(|_| self.out.write_all(b"\n"));
+
+        if let Err(e) = result {
+            self.error = Some(e);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    pub hits: BTreeMap<usize, u64>,
+    pub opcodes: BTreeMap<i64, u64>,
+    pub mnemonics: BTreeMap<i64, &'static str>,
+    pub total: u64,
+}
+
+impl Profile {
+    pub fn new() -> Profile {
+        Profile::default()
+    }
+
+    pub fn hottest(&self, count: usize) -> Vec<(usize, u64)> {
+        let mut hits: Vec<(usize, u64)> = self.hits.iter().map(|(address, hits)| (*address, *hits)).collect();
+
+        hits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits.truncate(count);
+
+        hits
+    }
+}
+
+impl<C> Observer<C> for Profile {
+    fn observe(&mut self, event: &Event<C>) {
+        *self.hits.entry(event.ip).or_insert(0) += 1;
+        *self.opcodes.entry(event.opcode).or_insert(0) += 1;
+        self.mnemonics.insert(event.opcode, event.mnemonic);
+        self.total += 1;
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "instructions executed :: {}", self.total)?;
+        writeln!(f, "opcode frequency:")?;
+
+        for (opcode, count) in self.opcodes.iter() {
+            let mnemonic = self.mnemonics.get(opcode).cloned().unwrap_or("DATA");
+
+            writeln!(f, "  {:<4} {:>10}", mnemonic, count)?;
+        }
+
+        writeln!(f, "hottest addresses:")?;
+
+        for (address, count) in self.hottest(10) {
+            writeln!(f, "  {:>6} {:>10}", address, count)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use intcode::{compile, History, InstructionSet, Machine, Queue, Status};
+
+type State = (Vec<(usize, i64)>, usize, i64, Status);
+
+fn state(machine: &Machine) -> State {
+    let cells = machine.memory().cells().filter(|(_, value)| **value != 0).map(|(address, value)| (address, *value)).collect();
+
+    (cells, machine.ip(), machine.relative_base(), machine.status().clone())
+}
+
+#[test]
+fn rewinds_across_checkpoints() {
+    let program = compile(
+        "
+        fn main() {
+            let total = 0;
+            let n = input();
+
+            while n > 0 {
+                total = total + input();
+                n = n - 1;
+            }
+
+            output(total);
+        }
+        ",
+    )
+    .unwrap();
+
+    let mut machine = Machine::new(program);
+    let mut queue = Queue::new(vec![3, 10, 20, 30]);
+    let mut history = History::new(4, 64);
+    let mut states = vec![state(&machine)];
+
+    while !machine.is_halted() {
+        history.step(&mut machine, &mut queue).unwrap();
+        states.push(state(&machine));
+    }
+
+    assert_eq!(queue.output(), &[60]);
+    assert!(history.steps() > 12);
+    assert_eq!(history.earliest(), 0);
+
+    while history.undo(&mut machine, &mut queue).is_some() {
+        states.pop();
+        assert_eq!(state(&machine), *states.last().unwrap(), "after undo to step {}", history.steps());
+    }
+
+    assert_eq!(states.len(), 1);
+    assert_eq!(history.steps(), 0);
+
+    machine.run(&mut queue).unwrap();
+
+    assert_eq!(queue.output(), &[60, 60]);
+}
+
+#[test]
+fn undoes_every_write() {
+    let mut set = InstructionSet::standard();
+
+    set.register(20, "SWAP", 2, &[1, 2], |context| {
+        let (left, right) = (context.read(1)?, context.read(2)?);
+
+        context.write(1, left + right)?;
+        context.write(2, left * 10)?;
+
+        Ok(())
+    });
+
+    let set = Arc::new(set);
+
+    for program in [vec![20, 5, 6, 99, 0, 1, 2], vec![20, 5, 5, 99, 0, 3]].iter() {
+        let mut machine = Machine::with_instruction_set(program.clone(), set.clone());
+        let mut queue = Queue::new(vec![]);
+        let mut history = History::new(1, 8);
+        let before = state(&machine);
+
+        history.step(&mut machine, &mut queue).unwrap();
+
+        assert_ne!(state(&machine), before);
+
+        let entry = history.undo(&mut machine, &mut queue).unwrap();
+
+        assert_eq!(entry.writes.len(), 2);
+        assert_eq!(state(&machine), before);
+    }
+}
@@ -0,0 +1,191 @@
+use intcode::{compile, Compiled, Machine};
+
+fn run(source: &str, input: Vec<i64>) -> Vec<i64> {
+    let program = compile(source).unwrap();
+    let interpreted = Machine::new(program.clone()).run_with_input(input.clone()).unwrap();
+    let compiled = Compiled::new(&program);
+    let fast = compiled.machine().run_with_input(input).unwrap();
+
+    assert_eq!(interpreted, fast);
+
+    interpreted
+}
+
+fn error(source: &str) -> String {
+    compile(source).unwrap_err().to_string()
+}
+
+#[test]
+fn arithmetic() {
+    let source = "
+        fn main() {
+            output(1 + 2 * 3);
+            output((1 + 2) * 3);
+            output(-4 - -6);
+            output(10 - 3 - 2);
+        }
+    ";
+
+    assert_eq!(run(source, vec![]), vec![7, 9, 2, 5]);
+}
+
+#[test]
+fn comparisons() {
+    let source = "
+        fn main() {
+            output(1 < 2);
+            output(2 < 1);
+            output(2 <= 2);
+            output(3 >= 4);
+            output(5 > 4);
+            output(5 == 5);
+            output(5 != 5);
+            output(!0);
+            output(!7);
+        }
+    ";
+
+    assert_eq!(run(source, vec![]), vec![1, 0, 1, 0, 1, 1, 0, 1, 0]);
+}
+
+#[test]
+fn branches() {
+    let source = "
+        fn sign(x) {
+            if x < 0 {
+                return -1;
+            } else if x == 0 {
+                return 0;
+            } else {
+                return 1;
+            }
+        }
+
+        fn main() {
+            output(sign(input()));
+            output(sign(input()));
+            output(sign(input()));
+        }
+    ";
+
+    assert_eq!(run(source, vec![-12, 0, 8]), vec![-1, 0, 1]);
+}
+
+#[test]
+fn loops() {
+    let source = "
+        fn main() {
+            let n = input();
+            let total = 0;
+
+            while n > 0 {
+                total = total + n;
+                n = n - 1;
+            }
+
+            output(total);
+        }
+    ";
+
+    assert_eq!(run(source, vec![100]), vec![5050]);
+}
+
+#[test]
+fn recursion() {
+    let source = "
+        fn factorial(n) {
+            if n <= 1 {
+                return 1;
+            }
+
+            return n * factorial(n - 1);
+        }
+
+        fn fib(n) {
+            if n < 2 {
+                return n;
+            }
+
+            return fib(n - 1) + fib(n - 2);
+        }
+
+        fn main() {
+            output(factorial(10));
+            output(fib(15));
+        }
+    ";
+
+    assert_eq!(run(source, vec![]), vec![3628800, 610]);
+}
+
+#[test]
+fn nested_calls() {
+    let source = "
+        fn add(a, b) {
+            return a + b;
+        }
+
+        fn mul(a, b) {
+            return a * b;
+        }
+
+        fn main() {
+            let x = 2;
+
+            output(add(mul(x, 3), add(x, mul(4, 5))));
+        }
+    ";
+
+    assert_eq!(run(source, vec![]), vec![28]);
+}
+
+#[test]
+fn short_circuit() {
+    let source = "
+        fn noisy(x) {
+            output(x);
+            return x;
+        }
+
+        fn main() {
+            output(noisy(0) && noisy(1));
+            output(noisy(2) || noisy(3));
+            output(noisy(4) && noisy(5));
+            output(noisy(0) || noisy(0));
+        }
+    ";
+
+    assert_eq!(run(source, vec![]), vec![0, 0, 2, 1, 4, 5, 1, 0, 0, 0]);
+}
+
+#[test]
+fn procedures() {
+    let source = "
+        // echo the inputs until a zero is read
+        fn echo() {
+            let value = input();
+
+            while value != 0 {
+                output(value);
+                value = input();
+            }
+        }
+
+        fn main() {
+            echo();
+            output(99);
+        }
+    ";
+
+    assert_eq!(run(source, vec![3, 1, 4, 0]), vec![3, 1, 4, 99]);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(error("fn main() { output(x); }"), "line 1: undefined variable 'x'");
+    assert_eq!(error("fn main() {\n    f();\n}"), "line 2: undefined function 'f'");
+    assert_eq!(error("fn f(a) { return a; }\nfn main() { f(); }"), "line 2: 'f' takes 1 arguments, found 0");
+    assert_eq!(error("fn f(a,\n     a) { return a; }\nfn main() { f(1, 2); }"), "line 2: duplicate parameter 'a'");
+    assert_eq!(error("fn f() {}"), "line 1: missing 'main' function");
+    assert_eq!(error("fn main() { let x = ; }"), "line 1: expected an expression, found ';'");
+}
@@ -0,0 +1,49 @@
+use intcode::{evaluate_symbolic, parse_input, Machine};
+
+#[test]
+fn runs_past_the_end() {
+    let error = evaluate_symbolic(&[1, 0, 0, 0]).unwrap_err();
+
+    assert_eq!(error.ip, 4);
+    assert_eq!(error.message, "ran past the end of the program");
+
+    let error = evaluate_symbolic(&[1, 0, 0, 0, 2, 0]).unwrap_err();
+
+    assert_eq!(error.ip, 4);
+    assert_eq!(error.message, "instruction runs past the end of the program");
+}
+
+#[test]
+fn overflow() {
+    let program = [1, 0, 0, 3, 2, 9, 9, 0, 99, 4000000000];
+    let error = evaluate_symbolic(&program).unwrap_err();
+
+    assert_eq!(error.ip, 4);
+    assert_eq!(error.message, "arithmetic overflow");
+    assert!(Machine::new(program.to_vec()).run_with_input(vec![]).is_err());
+}
+
+#[test]
+fn closed_form_overflow() {
+    let mut program = vec![1, 0, 0, 3, 1, 1, 2, 3];
+
+    for _ in 0..7 {
+        program.extend(&[2, 3, 3, 3]);
+    }
+
+    program.extend(&[1, 3, 3, 0, 99]);
+
+    let expr = evaluate_symbolic(&program).unwrap();
+
+    assert_eq!(expr.evaluate(99, 99), None);
+    assert_eq!(expr.to_polynomial(), None);
+}
+
+#[test]
+fn day02() {
+    let image = parse_input("../day02-2/src/opcodes.csv").unwrap();
+    let polynomial = evaluate_symbolic(&image).unwrap().to_polynomial().unwrap();
+
+    assert_eq!(polynomial.evaluate(12, 2), Some(9706670));
+    assert_eq!(polynomial.solve(19690720), vec![(25, 52)]);
+}